@@ -3,6 +3,7 @@
 extern crate burn;
 
 use std::os;
+use std::cmp;
 use std::io;
 use std::path::posix::Path;
 use burn::lang::origin;
@@ -205,16 +206,17 @@ struct Burn {
 			errln!( "in {} on line {}", error.get_origin().get_name(), line.no );
 			
 			if self.verbose {
-				self.print_error_line_fragment( source, line );
+				self.print_error_line_fragment( source, line, error.get_source_length() );
 			}
 		}
 		
-		fn print_error_line_fragment( &self, source: &str, line: util::source::Line ) {
+		fn print_error_line_fragment( &self, source: &str, line: util::source::Line, length: uint ) {
 			errln!( "{}", source.slice( line.start, line.end ) );
 			for c in source.slice( line.start, line.offset ).chars() {
 				let _ = io::stderr().write_char( if c == '\t' { c } else { ' ' } );
 			}
-			errln!( "^" );
+			let length = cmp::max( 1, cmp::min( length, source.slice( line.offset, line.end ).char_len() ) );
+			errln!( "{}", "^".repeat( length ) );
 		}
 	}
 
@@ -2,21 +2,36 @@ use lang::value;
 use lang::module::Module;
 use lang::special;
 use lang::special::{StaticSpecialDef, StaticSpecial, Special, RefCountedSpecial};
-use mem::rc::RefCounted;
+use lang::identifier::Identifier;
+use mem::rc::{Rc, RefCounted};
 
 pub fn create_module() -> Module {
 	let mut errors = Module::new();
 	errors.add( "TypeError", value::StaticSpecial( StaticSpecial::new( &TypeError ) ) );
 	errors.add( "ArgumentError", value::StaticSpecial( StaticSpecial::new( &ArgumentError ) ) );
+	errors.add( "AttributeError", value::StaticSpecial( StaticSpecial::new( &AttributeError ) ) );
+	errors.add( "DivisionByZeroError", value::StaticSpecial( StaticSpecial::new( &DivisionByZeroError ) ) );
+	errors.add( "IndexError", value::StaticSpecial( StaticSpecial::new( &IndexError ) ) );
+	errors.add( "OverflowError", value::StaticSpecial( StaticSpecial::new( &OverflowError ) ) );
 	errors.lock();
 	errors
 }
 
+/// All errors have a `message` property.
+fn get_message( message: &String, name: Identifier ) -> Option<value::Value> {
+	if name == Identifier::find_or_create( "message" ) {
+		Some( value::String( Rc::new( message.clone() ) ) )
+	} else {
+		None
+	}
+}
+
 
 
 static TypeError: StaticSpecialDef = StaticSpecialDef {
 	repr: "TypeError",
 	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
 	type_test: is_type_error,
 };
 
@@ -35,6 +50,7 @@ struct TypeError {
 		fn repr( &self ) -> String { "<TypeError>".into_string() }
 		fn to_string( &self ) -> String { format!( "TypeError: {}", self.message ) }
 		fn is_throwable( &self ) -> bool { true }
+		fn get_property( &self, name: Identifier ) -> Option<value::Value> { get_message( &self.message, name ) }
 	}
 	
 	impl RefCounted for TypeError {}
@@ -49,6 +65,7 @@ pub fn create_type_error( message: String ) -> value::Value {
 static ArgumentError: StaticSpecialDef = StaticSpecialDef {
 	repr: "ArgumentError",
 	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
 	type_test: is_argument_error,
 };
 
@@ -67,6 +84,148 @@ struct ArgumentError {
 		fn repr( &self ) -> String { "<ArgumentError>".into_string() }
 		fn to_string( &self ) -> String { format!( "ArgumentError: {}", self.message ) }
 		fn is_throwable( &self ) -> bool { true }
+		fn get_property( &self, name: Identifier ) -> Option<value::Value> { get_message( &self.message, name ) }
 	}
 	
 	impl RefCounted for ArgumentError {}
+	impl RefCountedSpecial for ArgumentError {}
+
+pub fn create_argument_error( message: String ) -> value::Value {
+	special::create_rc_value( ArgumentError { message: message } )
+}
+
+
+
+static AttributeError: StaticSpecialDef = StaticSpecialDef {
+	repr: "AttributeError",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_attribute_error,
+};
+
+fn is_attribute_error( value: &value::Value ) -> bool {
+	match *value {
+		value::RcSpecial( ref r ) => r.is::<AttributeError>(),
+		_ => false,
+	}
+}
+
+struct AttributeError {
+	message: String,
+}
+
+	impl Special for AttributeError {
+		fn repr( &self ) -> String { "<AttributeError>".into_string() }
+		fn to_string( &self ) -> String { format!( "AttributeError: {}", self.message ) }
+		fn is_throwable( &self ) -> bool { true }
+		fn get_property( &self, name: Identifier ) -> Option<value::Value> { get_message( &self.message, name ) }
+	}
+	
+	impl RefCounted for AttributeError {}
+	impl RefCountedSpecial for AttributeError {}
+
+pub fn create_attribute_error( message: String ) -> value::Value {
+	special::create_rc_value( AttributeError { message: message } )
+}
+
+
+
+static DivisionByZeroError: StaticSpecialDef = StaticSpecialDef {
+	repr: "DivisionByZeroError",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_division_by_zero_error,
+};
+
+fn is_division_by_zero_error( value: &value::Value ) -> bool {
+	match *value {
+		value::RcSpecial( ref r ) => r.is::<DivisionByZeroError>(),
+		_ => false,
+	}
+}
+
+struct DivisionByZeroError {
+	message: String,
+}
+
+	impl Special for DivisionByZeroError {
+		fn repr( &self ) -> String { "<DivisionByZeroError>".into_string() }
+		fn to_string( &self ) -> String { format!( "DivisionByZeroError: {}", self.message ) }
+		fn is_throwable( &self ) -> bool { true }
+		fn get_property( &self, name: Identifier ) -> Option<value::Value> { get_message( &self.message, name ) }
+	}
+	
+	impl RefCounted for DivisionByZeroError {}
+	impl RefCountedSpecial for DivisionByZeroError {}
+
+pub fn create_division_by_zero_error( message: String ) -> value::Value {
+	special::create_rc_value( DivisionByZeroError { message: message } )
+}
+
+
+
+static IndexError: StaticSpecialDef = StaticSpecialDef {
+	repr: "IndexError",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_index_error,
+};
+
+fn is_index_error( value: &value::Value ) -> bool {
+	match *value {
+		value::RcSpecial( ref r ) => r.is::<IndexError>(),
+		_ => false,
+	}
+}
+
+struct IndexError {
+	message: String,
+}
+
+	impl Special for IndexError {
+		fn repr( &self ) -> String { "<IndexError>".into_string() }
+		fn to_string( &self ) -> String { format!( "IndexError: {}", self.message ) }
+		fn is_throwable( &self ) -> bool { true }
+		fn get_property( &self, name: Identifier ) -> Option<value::Value> { get_message( &self.message, name ) }
+	}
+	
+	impl RefCounted for IndexError {}
+	impl RefCountedSpecial for IndexError {}
+
+pub fn create_index_error( message: String ) -> value::Value {
+	special::create_rc_value( IndexError { message: message } )
+}
+
+
+
+static OverflowError: StaticSpecialDef = StaticSpecialDef {
+	repr: "OverflowError",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_overflow_error,
+};
+
+fn is_overflow_error( value: &value::Value ) -> bool {
+	match *value {
+		value::RcSpecial( ref r ) => r.is::<OverflowError>(),
+		_ => false,
+	}
+}
+
+struct OverflowError {
+	message: String,
+}
+
+	impl Special for OverflowError {
+		fn repr( &self ) -> String { "<OverflowError>".into_string() }
+		fn to_string( &self ) -> String { format!( "OverflowError: {}", self.message ) }
+		fn is_throwable( &self ) -> bool { true }
+		fn get_property( &self, name: Identifier ) -> Option<value::Value> { get_message( &self.message, name ) }
+	}
+	
+	impl RefCounted for OverflowError {}
+	impl RefCountedSpecial for OverflowError {}
+
+pub fn create_overflow_error( message: String ) -> value::Value {
+	special::create_rc_value( OverflowError { message: message } )
+}
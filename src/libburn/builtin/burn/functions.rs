@@ -0,0 +1,137 @@
+use lang::value;
+use lang::special;
+use lang::special::{Special, RefCountedSpecial};
+use lang::module::Module;
+use lang::identifier::Identifier;
+use lang::function::RustFunction;
+use lang::operations;
+use builtin::burn::errors::{create_argument_error, create_overflow_error};
+use mem::rc::RefCounted;
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
+
+pub fn create_module() -> Module {
+	let mut functions = Module::new();
+	functions.add( "range", special::create_rc_value( RustFunction::new( range ) ) );
+	functions.lock();
+	functions
+}
+
+
+
+/// `range( start, end )` counts from `start` up to, but not including, `end`.
+fn range(
+	_: &mut VirtualMachine,
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> rust::Result {
+	
+	let arguments = try_arguments!( &["start", "end"], arguments, keyword_arguments );
+	
+	let mut bounds = Vec::new();
+	for argument in arguments.iter() {
+		match *argument {
+			value::Integer( i ) => { bounds.push( i ); }
+			value::BigInteger(..) => {
+				return rust::Throw( create_overflow_error( "`range` bounds must fit in 64 bits".to_string() ) );
+			}
+			_ => {
+				return rust::Throw( create_argument_error(
+					format!( "`range` expects an Integer, got {}", operations::repr( argument ) )
+				) );
+			}
+		}
+	}
+	
+	rust::Ok( special::create_rc_value( Range {
+		start: *bounds.get( 0 ),
+		end: *bounds.get( 1 ),
+	} ) )
+}
+
+struct Range {
+	start: i64,
+	end: i64,
+}
+
+	impl Special for Range {
+		fn repr( &self ) -> String { "<Range>".into_string() }
+		fn is_truthy( &self ) -> bool { self.start < self.end }
+		
+		fn iterate( &self ) -> Option<value::Value> {
+			Some( special::create_rc_value( RangeIterator {
+				next: self.start,
+				end: self.end,
+			} ) )
+		}
+		
+		fn get_property( &self, name: Identifier ) -> Option<value::Value> {
+			let mut name = name;
+			match name.get_value() {
+				"start" => Some( value::Integer( self.start ) ),
+				"end" => Some( value::Integer( self.end ) ),
+				_ => None,
+			}
+		}
+		
+		fn has_method( &self, name: Identifier ) -> bool {
+			let mut name = name;
+			match name.get_value() {
+				"contains" => true,
+				_ => false,
+			}
+		}
+		
+		fn call_method(
+			&self,
+			_: &mut VirtualMachine,
+			name: Identifier,
+			arguments: Vec<value::Value>,
+			keyword_arguments: Vec<(Identifier, value::Value)>
+		) -> rust::Result {
+			
+			let mut method_name = name;
+			match method_name.get_value() {
+				
+				"contains" => {
+					let arguments = try_arguments!( &["value"], arguments, keyword_arguments );
+					let contains = match *arguments.get( 0 ) {
+						value::Integer( i ) => self.start <= i && i < self.end,
+						// the bounds fit in 64 bits, so big integers are outside
+						value::BigInteger(..) => false,
+						_ => false,
+					};
+					rust::Ok( value::Boolean( contains ) )
+				}
+				
+				_ => { unreachable!(); }
+			}
+		}
+	}
+	
+	impl RefCounted for Range {}
+	impl RefCountedSpecial for Range {}
+
+struct RangeIterator {
+	next: i64,
+	end: i64,
+}
+
+	impl Special for RangeIterator {
+		fn repr( &self ) -> String { "<Iterator>".into_string() }
+		fn is_iterator( &self ) -> bool { true }
+		
+		fn next( &mut self ) -> Option<value::Value> {
+			
+			if self.next >= self.end {
+				return None;
+			}
+			
+			let next = self.next;
+			self.next += 1;
+			Some( value::Integer( next ) )
+		}
+	}
+	
+	impl RefCounted for RangeIterator {}
+	impl RefCountedSpecial for RangeIterator {}
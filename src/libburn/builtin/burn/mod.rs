@@ -1,6 +1,8 @@
 use lang::module::Module;
 
 pub mod errors;
+pub mod functions;
+pub mod strings;
 pub mod types;
 
 pub fn create_module() -> Module {
@@ -8,6 +10,8 @@ pub fn create_module() -> Module {
 	
 	let types = box types::create_module();
 	let errors = box errors::create_module();
+	let functions = box functions::create_module();
+	let strings = box strings::create_module();
 	
 	let mut implicit = box Module::new();
 	implicit.add( "Boolean", types.get( "Boolean" ) );
@@ -15,13 +19,25 @@ pub fn create_module() -> Module {
 	implicit.add( "Float", types.get( "Float" ) );
 	implicit.add( "Number", types.get( "Number" ) );
 	implicit.add( "String", types.get( "String" ) );
+	implicit.add( "List", types.get( "List" ) );
+	implicit.add( "Map", types.get( "Map" ) );
 	implicit.add( "Type", types.get( "Type" ) );
+	implicit.add( "Positive", types.get( "Positive" ) );
+	implicit.add( "Negative", types.get( "Negative" ) );
+	implicit.add( "Empty", types.get( "Empty" ) );
 	implicit.add( "ArgumentError", errors.get( "ArgumentError" ) );
+	implicit.add( "AttributeError", errors.get( "AttributeError" ) );
+	implicit.add( "DivisionByZeroError", errors.get( "DivisionByZeroError" ) );
+	implicit.add( "IndexError", errors.get( "IndexError" ) );
+	implicit.add( "OverflowError", errors.get( "OverflowError" ) );
 	implicit.add( "TypeError", errors.get( "TypeError" ) );
+	implicit.add( "range", functions.get( "range" ) );
 	implicit.lock();
 	
 	burn.add_module( "types", types );
 	burn.add_module( "errors", errors );
+	burn.add_module( "functions", functions );
+	burn.add_module( "strings", strings );
 	burn.add_module( "implicit", implicit );
 	
 	burn.lock();
@@ -0,0 +1,79 @@
+use mem::rc::Rc;
+use lang::value;
+use lang::special;
+use lang::module::Module;
+use lang::identifier::Identifier;
+use lang::function::RustFunction;
+use lang::operations;
+use lang::string;
+use builtin::burn::errors::{create_argument_error, create_overflow_error};
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
+
+pub fn create_module() -> Module {
+	let mut strings = Module::new();
+	strings.add( "join", special::create_rc_value( RustFunction::new( join ) ) );
+	strings.add( "repeat", special::create_rc_value( RustFunction::new( repeat ) ) );
+	strings.lock();
+	strings
+}
+
+
+
+/// `join( separator, items )` joins a list of strings, like `separator.join( items )`.
+fn join(
+	_: &mut VirtualMachine,
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> rust::Result {
+	
+	let arguments = try_arguments!( &["separator", "items"], arguments, keyword_arguments );
+	
+	let separator = match *arguments.get( 0 ) {
+		value::String( ref s ) => s.clone(),
+		ref other => {
+			return rust::Throw( create_argument_error(
+				format!( "`join` expects a String, got {}", operations::repr( other ) )
+			) );
+		}
+	};
+	
+	match string::join( separator.as_slice(), arguments.get( 1 ) ) {
+		Ok( joined ) => rust::Ok( value::String( Rc::new( joined ) ) ),
+		Err( throwable ) => rust::Throw( throwable ),
+	}
+}
+
+/// `repeat( string, times )` concatenates `times` copies of `string`.
+fn repeat(
+	_: &mut VirtualMachine,
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> rust::Result {
+	
+	let arguments = try_arguments!( &["string", "times"], arguments, keyword_arguments );
+	
+	match (arguments.get( 0 ), arguments.get( 1 )) {
+		(&value::String( ref s ), &value::Integer( times )) if times >= 0 => {
+			rust::Ok( value::String( Rc::new( s.as_slice().repeat( times as uint ) ) ) )
+		}
+		(&value::String(..), &value::Integer( times )) => {
+			rust::Throw( create_argument_error( format!( "Can't repeat a String {} times", times ) ) )
+		}
+		(&value::String(..), &value::BigInteger( ref times )) => {
+			rust::Throw( create_overflow_error(
+				format!( "Can't repeat a String {} times: the count must fit in 64 bits", times.to_string() )
+			) )
+		}
+		(&value::String(..), other) => {
+			rust::Throw( create_argument_error(
+				format!( "`repeat` expects an Integer, got {}", operations::repr( other ) )
+			) )
+		}
+		(other, _) => {
+			rust::Throw( create_argument_error(
+				format!( "`repeat` expects a String, got {}", operations::repr( other ) )
+			) )
+		}
+	}
+}
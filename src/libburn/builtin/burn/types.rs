@@ -1,7 +1,15 @@
+use std::cmp::{Less, Equal, Greater};
+use std::num::Signed;
 use lang::value;
 use lang::special;
-use lang::special::{StaticSpecialDef, StaticSpecial};
+use lang::special::{Special, RefCountedSpecial, StaticSpecialDef, StaticSpecial};
 use lang::module::Module;
+use lang::identifier::Identifier;
+use lang::operations;
+use builtin::burn::errors::{create_argument_error, create_overflow_error};
+use mem::rc::RefCounted;
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
 
 pub fn create_module() -> Module {
 	let mut types = Module::new();
@@ -10,8 +18,13 @@ pub fn create_module() -> Module {
 	types.add( "Float", value::StaticSpecial( StaticSpecial::new( &Float ) ) );
 	types.add( "Number", value::StaticSpecial( StaticSpecial::new( &Number ) ) );
 	types.add( "String", value::StaticSpecial( StaticSpecial::new( &String ) ) );
+	types.add( "List", value::StaticSpecial( StaticSpecial::new( &List ) ) );
+	types.add( "Map", value::StaticSpecial( StaticSpecial::new( &Map ) ) );
 	types.add( "Type", value::StaticSpecial( StaticSpecial::new( &Type ) ) );
 	types.add( "Throwable", value::StaticSpecial( StaticSpecial::new( &Throwable ) ) );
+	types.add( "Positive", value::StaticSpecial( StaticSpecial::new( &Positive ) ) );
+	types.add( "Negative", value::StaticSpecial( StaticSpecial::new( &Negative ) ) );
+	types.add( "Empty", value::StaticSpecial( StaticSpecial::new( &Empty ) ) );
 	types.lock();
 	types
 }
@@ -20,7 +33,8 @@ pub fn create_module() -> Module {
 
 static Boolean: StaticSpecialDef = StaticSpecialDef {
 	repr: "Boolean",
-	has_method: special::static_has_no_methods,
+	has_method: has_boolean_refinement,
+	call_method: refine,
 	type_test: is_boolean,
 };
 
@@ -35,13 +49,14 @@ fn is_boolean( value: &value::Value ) -> bool {
 
 static Integer: StaticSpecialDef = StaticSpecialDef {
 	repr: "Integer",
-	has_method: special::static_has_no_methods,
+	has_method: has_number_refinement,
+	call_method: refine,
 	type_test: is_integer,
 };
 
 fn is_integer( value: &value::Value ) -> bool {
 	match *value {
-		value::Integer(..) => true,
+		value::Integer(..) | value::BigInteger(..) => true,
 		_ => false,
 	}
 }
@@ -50,7 +65,8 @@ fn is_integer( value: &value::Value ) -> bool {
 
 static Float: StaticSpecialDef = StaticSpecialDef {
 	repr: "Float",
-	has_method: special::static_has_no_methods,
+	has_method: has_number_refinement,
+	call_method: refine,
 	type_test: is_float,
 };
 
@@ -65,13 +81,14 @@ fn is_float( value: &value::Value ) -> bool {
 
 static Number: StaticSpecialDef = StaticSpecialDef {
 	repr: "Number",
-	has_method: special::static_has_no_methods,
+	has_method: has_number_refinement,
+	call_method: refine,
 	type_test: is_number,
 };
 
 fn is_number( value: &value::Value ) -> bool {
 	match *value {
-		value::Integer(..) | value::Float(..) => true,
+		value::Integer(..) | value::BigInteger(..) | value::Float(..) => true,
 		_ => false,
 	}
 }
@@ -80,7 +97,8 @@ fn is_number( value: &value::Value ) -> bool {
 
 static String: StaticSpecialDef = StaticSpecialDef {
 	repr: "String",
-	has_method: special::static_has_no_methods,
+	has_method: has_string_refinement,
+	call_method: refine,
 	type_test: is_string,
 };
 
@@ -93,9 +111,42 @@ fn is_string( value: &value::Value ) -> bool {
 
 
 
+static List: StaticSpecialDef = StaticSpecialDef {
+	repr: "List",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_list,
+};
+
+fn is_list( value: &value::Value ) -> bool {
+	match *value {
+		value::List(..) => true,
+		_ => false,
+	}
+}
+
+
+
+static Map: StaticSpecialDef = StaticSpecialDef {
+	repr: "Map",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_map,
+};
+
+fn is_map( value: &value::Value ) -> bool {
+	match *value {
+		value::Map(..) => true,
+		_ => false,
+	}
+}
+
+
+
 static Type: StaticSpecialDef = StaticSpecialDef {
 	repr: "Type",
 	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
 	type_test: is_type,
 };
 
@@ -103,6 +154,7 @@ pub fn is_type( value: &value::Value ) -> bool {
 	match *value {
 		value::TypeUnion(..) => true,
 		value::TypeIntersection(..) => true,
+		value::TypeComplement(..) => true,
 		value::StaticSpecial( special ) => special.is_type(),
 		value::RcSpecial( ref r ) => r.is_type(),
 		_ => false,
@@ -114,6 +166,7 @@ pub fn is_type( value: &value::Value ) -> bool {
 static Throwable: StaticSpecialDef = StaticSpecialDef {
 	repr: "Throwable",
 	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
 	type_test: is_throwable,
 };
 
@@ -123,3 +176,272 @@ pub fn is_throwable( value: &value::Value ) -> bool {
 		_ => false,
 	}
 }
+
+
+
+static Positive: StaticSpecialDef = StaticSpecialDef {
+	repr: "Positive",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_positive,
+};
+
+fn is_positive( value: &value::Value ) -> bool {
+	match *value {
+		value::Integer( i ) => i > 0,
+		value::BigInteger( ref i ) => i.is_positive(),
+		value::Float( f ) => f > 0f64,
+		_ => false,
+	}
+}
+
+
+
+static Negative: StaticSpecialDef = StaticSpecialDef {
+	repr: "Negative",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_negative,
+};
+
+fn is_negative( value: &value::Value ) -> bool {
+	match *value {
+		value::Integer( i ) => i < 0,
+		value::BigInteger( ref i ) => i.is_negative(),
+		value::Float( f ) => f < 0f64,
+		_ => false,
+	}
+}
+
+
+
+static Empty: StaticSpecialDef = StaticSpecialDef {
+	repr: "Empty",
+	has_method: special::static_has_no_methods,
+	call_method: special::static_call_no_methods,
+	type_test: is_empty,
+};
+
+fn is_empty( value: &value::Value ) -> bool {
+	match *value {
+		value::String( ref s ) => s.len() == 0,
+		value::List( ref l ) => l.items.len() == 0,
+		value::Map( ref m ) => m.len() == 0,
+		_ => false,
+	}
+}
+
+
+
+// Refinements
+
+macro_rules! try_number(
+	( $name:expr, $argument:expr ) => {{
+		match *$argument {
+			value::Integer(..) | value::BigInteger(..) | value::Float(..) => $argument.clone(),
+			ref argument @ _ => {
+				return rust::Throw( create_argument_error(
+					format!( "`{}` expects a Number, got {}", $name, operations::repr( argument ) )
+				) );
+			}
+		}
+	}}
+)
+
+macro_rules! try_string(
+	( $name:expr, $argument:expr ) => {{
+		match *$argument {
+			value::String( ref s ) => s.as_slice().to_string(),
+			ref argument @ _ => {
+				return rust::Throw( create_argument_error(
+					format!( "`{}` expects a String, got {}", $name, operations::repr( argument ) )
+				) );
+			}
+		}
+	}}
+)
+
+fn has_boolean_refinement( name: Identifier ) -> bool {
+	let mut name = name;
+	match name.get_value() {
+		"one_of" => true,
+		_ => false,
+	}
+}
+
+fn has_number_refinement( name: Identifier ) -> bool {
+	let mut name = name;
+	match name.get_value() {
+		"between" | "above" | "below" | "one_of" => true,
+		_ => false,
+	}
+}
+
+fn has_string_refinement( name: Identifier ) -> bool {
+	let mut name = name;
+	match name.get_value() {
+		"starting_with" | "ending_with" | "of_length" | "one_of" => true,
+		_ => false,
+	}
+}
+
+/// Create a refinement of a base type, e.g. `String.starting_with( "http://" )`.
+fn refine(
+	base: StaticSpecial,
+	_: &mut VirtualMachine,
+	name: Identifier,
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> rust::Result {
+	
+	let mut method_name = name;
+	let method_name = method_name.get_value();
+	
+	let refinement = match method_name {
+		
+		"between" => {
+			let arguments = try_arguments!( &["min", "max"], arguments, keyword_arguments );
+			let min = try_number!( method_name, arguments.get( 0 ) );
+			let max = try_number!( method_name, arguments.get( 1 ) );
+			Between( min, max )
+		}
+		
+		"above" => {
+			let arguments = try_arguments!( &["min"], arguments, keyword_arguments );
+			Above( try_number!( method_name, arguments.get( 0 ) ) )
+		}
+		
+		"below" => {
+			let arguments = try_arguments!( &["max"], arguments, keyword_arguments );
+			Below( try_number!( method_name, arguments.get( 0 ) ) )
+		}
+		
+		"starting_with" => {
+			let arguments = try_arguments!( &["prefix"], arguments, keyword_arguments );
+			StartingWith( try_string!( method_name, arguments.get( 0 ) ) )
+		}
+		
+		"ending_with" => {
+			let arguments = try_arguments!( &["suffix"], arguments, keyword_arguments );
+			EndingWith( try_string!( method_name, arguments.get( 0 ) ) )
+		}
+		
+		"of_length" => {
+			let arguments = try_arguments!( &["length"], arguments, keyword_arguments );
+			match *arguments.get( 0 ) {
+				value::Integer( i ) if i >= 0 => OfLength( i as uint ),
+				value::BigInteger(..) => {
+					return rust::Throw( create_overflow_error( "`of_length` lengths must fit in 64 bits".to_string() ) );
+				}
+				ref argument @ _ => {
+					return rust::Throw( create_argument_error(
+						format!( "`of_length` expects a length, got {}", operations::repr( argument ) )
+					) );
+				}
+			}
+		}
+		
+		"one_of" => {
+			// any number of values, so there are no parameters to name
+			match keyword_arguments.iter().next() {
+				Some( &(name, _) ) => {
+					return rust::Throw( create_argument_error( format!( "Unknown keyword argument `{}`", name ) ) );
+				}
+				None => {}
+			}
+			OneOf( arguments )
+		}
+		
+		_ => { unreachable!(); }
+	};
+	
+	rust::Ok( special::create_rc_value( RefinedType {
+		base: base,
+		refinement: refinement,
+	} ) )
+}
+
+/// A base type whose values are further restricted.
+struct RefinedType {
+	base: StaticSpecial,
+	refinement: Refinement,
+}
+
+	enum Refinement {
+		Between( value::Value, value::Value ),
+		Above( value::Value ),
+		Below( value::Value ),
+		StartingWith( String ),
+		EndingWith( String ),
+		OfLength( uint ),
+		OneOf( Vec<value::Value> ),
+	}
+	
+	impl Special for RefinedType {
+		fn repr( &self ) -> String { "<Type>".into_string() }
+		fn is_type( &self ) -> bool { true }
+		
+		fn type_test( &self, value: &value::Value ) -> bool {
+			
+			if ! self.base.type_test( value ) {
+				return false;
+			}
+			
+			match self.refinement {
+				
+				Between( ref min, ref max ) => {
+					let is_above_min = match operations::try_compare( value, min ) {
+						Ok( Some( Greater ) ) | Ok( Some( Equal ) ) => true,
+						_ => false,
+					};
+					let is_below_max = match operations::try_compare( value, max ) {
+						Ok( Some( Less ) ) | Ok( Some( Equal ) ) => true,
+						_ => false,
+					};
+					is_above_min && is_below_max
+				}
+				
+				Above( ref min ) => {
+					match operations::try_compare( value, min ) {
+						Ok( Some( Greater ) ) => true,
+						_ => false,
+					}
+				}
+				
+				Below( ref max ) => {
+					match operations::try_compare( value, max ) {
+						Ok( Some( Less ) ) => true,
+						_ => false,
+					}
+				}
+				
+				StartingWith( ref prefix ) => {
+					match *value {
+						value::String( ref s ) => s.as_slice().starts_with( prefix.as_slice() ),
+						_ => false,
+					}
+				}
+				
+				EndingWith( ref suffix ) => {
+					match *value {
+						value::String( ref s ) => s.as_slice().ends_with( suffix.as_slice() ),
+						_ => false,
+					}
+				}
+				
+				OfLength( length ) => {
+					match *value {
+						value::String( ref s ) => s.as_slice().char_len() == length,
+						_ => false,
+					}
+				}
+				
+				OneOf( ref values ) => {
+					values.iter().any( |v| { operations::equals( value, v ) } )
+				}
+			}
+		}
+	}
+	
+	impl RefCounted for RefinedType {}
+	impl RefCountedSpecial for RefinedType {}
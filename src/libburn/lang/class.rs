@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use mem::rc::{Rc, RefCounted};
+use mem::gc::{Gc, GarbageCollected};
+use lang::value;
+use lang::identifier::Identifier;
+use lang::function::Function;
+
+pub struct Class {
+	#[doc(hidden)]
+	pub definition: Rc<ClassDefinition>,
+	#[doc(hidden)]
+	pub bases: Vec<Gc<Class>>,
+	/// The method resolution order of this class, excluding the class itself.
+	#[doc(hidden)]
+	pub ancestors: Vec<Gc<Class>>,
+	#[doc(hidden)]
+	pub properties: Vec<(Identifier, value::Value)>,
+	#[doc(hidden)]
+	pub methods: HashMap<Identifier, Gc<Function>>,
+}
+
+	impl Class {
+		
+		pub fn new( definition: Rc<ClassDefinition>, bases: Vec<Gc<Class>> ) -> Result<Class,()> {
+			
+			let ancestors = try!( linearize( bases.as_slice() ) );
+			
+			Ok( Class {
+				definition: definition,
+				bases: bases,
+				ancestors: ancestors,
+				properties: Vec::new(),
+				methods: HashMap::new(),
+			} )
+		}
+		
+		pub fn find_method( &self, name: Identifier ) -> Option<Gc<Function>> {
+			
+			match self.methods.find( &name ) {
+				Some( method ) => { return Some( method.clone() ); }
+				None => {}
+			}
+			
+			find_method_in( self.ancestors.as_slice(), name )
+		}
+		
+		/// Find a method in the classes that follow `class` in this class's method resolution order.
+		/// If `class` is this class itself, all ancestors are searched.
+		pub fn find_next_method( &self, class: &Gc<Class>, name: Identifier ) -> Option<Gc<Function>> {
+			match self.ancestors.iter().position( |ancestor| { ancestor == class } ) {
+				Some( i ) => find_method_in( self.ancestors.slice_from( i + 1 ), name ),
+				None => find_method_in( self.ancestors.as_slice(), name ),
+			}
+		}
+	}
+	
+	impl GarbageCollected for Class {
+		
+		fn mark( &mut self ) {
+			unimplemented!();
+		}
+	}
+
+fn find_method_in( classes: &[Gc<Class>], name: Identifier ) -> Option<Gc<Function>> {
+
+	for class in classes.iter() {
+		match class.methods.find( &name ) {
+			Some( method ) => { return Some( method.clone() ); }
+			None => {}
+		}
+	}
+	
+	None
+}
+
+/// Compute the C3 linearization of a class with the given bases, excluding the class itself.
+///
+/// Every class comes before its bases, and the order of the bases is preserved.
+/// Fails if the hierarchy does not allow such an order.
+///
+/// See http://www.python.org/download/releases/2.3/mro/
+pub fn linearize( bases: &[Gc<Class>] ) -> Result<Vec<Gc<Class>>,()> {
+
+	let mut sequences = Vec::<Vec<Gc<Class>>>::new();
+	
+	for base in bases.iter() {
+		let mut sequence = vec!( base.clone() );
+		sequence.push_all( base.ancestors.as_slice() );
+		sequences.push( sequence );
+	}
+	
+	sequences.push( Vec::from_slice( bases ) );
+	
+	let mut linearization = Vec::new();
+	
+	loop {
+		
+		sequences.retain( |sequence| { sequence.len() > 0 } );
+		
+		if sequences.len() == 0 {
+			return Ok( linearization );
+		}
+		
+		// the next class is the first head that does not appear in the tail of any sequence
+		let mut next = None;
+		
+		for sequence in sequences.iter() {
+			
+			let head = sequence.get( 0 );
+			
+			let is_in_tail = sequences.iter().any( |other| {
+				other.slice_from( 1 ).contains( head )
+			} );
+			
+			if ! is_in_tail {
+				next = Some( head.clone() );
+				break;
+			}
+		}
+		
+		let next = match next {
+			Some( next ) => next,
+			None => { return Err( () ); }
+		};
+		
+		for sequence in sequences.mut_iter() {
+			if *sequence.get( 0 ) == next {
+				sequence.shift();
+			}
+		}
+		
+		linearization.push( next );
+	}
+}
+
+pub struct ClassDefinition {
+	pub n_bases: uint,
+	pub properties: Vec<Identifier>,
+	pub methods: Vec<Identifier>,
+}
+
+	impl ClassDefinition {
+		
+		pub fn new( n_bases: uint, properties: Vec<Identifier>, methods: Vec<Identifier> ) -> ClassDefinition {
+			ClassDefinition {
+				n_bases: n_bases,
+				properties: properties,
+				methods: methods,
+			}
+		}
+	}
+	
+	impl RefCounted for ClassDefinition {}
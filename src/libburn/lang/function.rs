@@ -1,8 +1,14 @@
 use std::vec::Vec;
 use mem::rc::{Rc, RefCounted};
-use mem::gc::GarbageCollected;
+use mem::gc::{Gc, GarbageCollected};
 use lang::value;
 use lang::identifier::Identifier;
+use lang::class::Class;
+use lang::special::{Special, RefCountedSpecial};
+use lang::operations;
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
+use builtin::burn::errors::{create_argument_error, create_type_error};
 use vm::bytecode::code::Code;
 
 pub struct Function {
@@ -12,6 +18,9 @@ pub struct Function {
 	pub static_bound_variables: Vec<value::Value>,
 	#[doc(hidden)]
 	pub shared_bound_variables: Vec<Rc<value::Value>>,
+	/// The class this function is a method of, if any.
+	#[doc(hidden)]
+	pub class: Option<Gc<Class>>,
 }
 
 	impl Function {
@@ -28,6 +37,7 @@ pub struct Function {
 				definition: definition,
 				static_bound_variables: Vec::from_elem( n_static, value::Nothing ),
 				shared_bound_variables: Vec::from_fn( n_shared, |_| { Rc::new( value::Nothing ) } ),
+				class: None,
 			}
 		}
 	}
@@ -39,8 +49,16 @@ pub struct Function {
 		}
 	}
 
+/// Which arguments a call gave is recorded in one bit per parameter, which limits their number.
+pub static MAX_PARAMETERS: uint = 64;
+
 pub struct FunctionDefinition {
 	pub parameters: Vec<FunctionParameterDefinition>,
+	/// The names of `parameters`, for matching keyword arguments.
+	pub parameter_names: Vec<Identifier>,
+	/// Where to store the list of extra positional arguments, if the function accepts them.
+	pub rest: Option<FunctionParameterStorage>,
+	pub receiver: Option<FunctionParameterStorage>,
 	pub bindings: Vec<FunctionBindingDefinition>,
 	pub code: Box<Code>,
 	pub n_static_bound_variables: uint,
@@ -51,6 +69,8 @@ pub struct FunctionDefinition {
 		pub fn new(
 			code: Box<Code>,
 			parameters: Vec<FunctionParameterDefinition>,
+			rest: Option<FunctionParameterStorage>,
+			receiver: Option<FunctionParameterStorage>,
 			bindings: Vec<FunctionBindingDefinition>
 		) -> FunctionDefinition {
 			
@@ -61,8 +81,13 @@ pub struct FunctionDefinition {
 				}
 			} ).count();
 			
+			let parameter_names = parameters.iter().map( |p| { p.name } ).collect();
+			
 			FunctionDefinition {
 				parameters: parameters,
+				parameter_names: parameter_names,
+				rest: rest,
+				receiver: receiver,
 				bindings: bindings,
 				code: code,
 				n_static_bound_variables: n_static_bound_variables,
@@ -72,9 +97,112 @@ pub struct FunctionDefinition {
 	
 	impl RefCounted for FunctionDefinition {}
 
+/// Match positional and keyword arguments to parameters, by position and by name.
+///
+/// Throws an `ArgumentError` if there are too many positional arguments,
+/// if a keyword doesn't name a parameter, or if a parameter is given more than once.
+pub fn match_arguments(
+	parameter_names: &[Identifier],
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> Result<Vec<Option<value::Value>>,value::Value> {
+	
+	try!( check_argument_count( parameter_names.len(), arguments.len() ) );
+	
+	let mut matched = Vec::from_fn( parameter_names.len(), |_| { None } );
+	
+	for (i, argument) in arguments.move_iter().enumerate() {
+		*matched.get_mut( i ) = Some( argument );
+	}
+	
+	for (name, argument) in keyword_arguments.move_iter() {
+		
+		let i = match parameter_names.iter().position( |n| { *n == name } ) {
+			Some( i ) => i,
+			None => {
+				return Err( create_argument_error( format!( "Unknown keyword argument `{}`", name ) ) );
+			}
+		};
+		
+		if matched.get( i ).is_some() {
+			return Err( create_argument_error( format!( "Duplicate argument ${}", name ) ) );
+		}
+		
+		*matched.get_mut( i ) = Some( argument );
+	}
+	
+	Ok( matched )
+}
+
+/// Throws an `ArgumentError` if there are more positional arguments than parameters.
+pub fn check_argument_count( n_parameters: uint, n_arguments: uint ) -> Result<(),value::Value> {
+	if n_arguments > n_parameters {
+		return Err( create_argument_error( format!(
+			"Too many arguments: expected at most {}, got {}", n_parameters, n_arguments
+		) ) );
+	}
+	Ok( () )
+}
+
+/// Expand the arguments at the given positions, which have to be lists, into their items.
+///
+/// The positions are in ascending order, as the compiler records them.
+pub fn spread_arguments( arguments: Vec<value::Value>, positions: &[uint] ) -> Result<Vec<value::Value>,value::Value> {
+	
+	let mut expanded = Vec::with_capacity( arguments.len() );
+	let mut positions = positions.iter().peekable();
+	
+	for (i, argument) in arguments.move_iter().enumerate() {
+		
+		match positions.peek() {
+			Some( &&position ) if position == i => { positions.next(); }
+			_ => {
+				expanded.push( argument );
+				continue;
+			}
+		}
+		
+		match argument {
+			value::List( ref list ) => {
+				expanded.push_all( list.items.as_slice() );
+			}
+			_ => {
+				return Err( create_type_error( format!( "Can't spread {}: not a List", operations::repr( &argument ) ) ) );
+			}
+		}
+	}
+	
+	Ok( expanded )
+}
+
+/// Match arguments like `match_arguments`, for functions implemented in Rust, where every parameter is required.
+pub fn match_rust_arguments(
+	parameter_names: &[&str],
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> Result<Vec<value::Value>,value::Value> {
+	
+	let names = parameter_names.iter().map( |name| { Identifier::find_or_create( *name ) } ).collect::<Vec<Identifier>>();
+	let matched = try!( match_arguments( names.as_slice(), arguments, keyword_arguments ) );
+	
+	let mut values = Vec::new();
+	for (name, argument) in parameter_names.iter().zip( matched.move_iter() ) {
+		match argument {
+			Some( argument ) => { values.push( argument ); }
+			None => {
+				return Err( create_argument_error( format!( "Missing argument ${}", name ) ) );
+			}
+		}
+	}
+	
+	Ok( values )
+}
+
 pub struct FunctionParameterDefinition {
 	pub name: Identifier,
 	pub storage: FunctionParameterStorage,
+	/// The default is evaluated by the function's own code when the argument is omitted.
+	pub has_default: bool,
 }
 
 pub enum FunctionParameterStorage {
@@ -88,3 +216,88 @@ pub enum FunctionBindingDefinition {
 	StaticBoundToStaticBoundBinding( uint, uint ),
 	SharedBoundToSharedBoundBinding( uint, uint ),
 }
+
+pub struct BoundMethod {
+	#[doc(hidden)]
+	pub receiver: value::Value,
+	#[doc(hidden)]
+	pub function: Gc<Function>,
+}
+
+	impl BoundMethod {
+		
+		pub fn new( receiver: value::Value, function: Gc<Function> ) -> BoundMethod {
+			BoundMethod {
+				receiver: receiver,
+				function: function,
+			}
+		}
+	}
+	
+	impl RefCounted for BoundMethod {}
+
+/// A method of a built-in value, bound to that value.
+pub struct BuiltinMethod {
+	receiver: value::Value,
+	name: Identifier,
+}
+
+	impl BuiltinMethod {
+		
+		pub fn new( receiver: value::Value, name: Identifier ) -> BuiltinMethod {
+			BuiltinMethod {
+				receiver: receiver,
+				name: name,
+			}
+		}
+	}
+	
+	impl Special for BuiltinMethod {
+		fn repr( &self ) -> String { "<Function>".into_string() }
+		fn is_callable( &self ) -> bool { true }
+		
+		fn call(
+			&self,
+			vm: &mut VirtualMachine,
+			arguments: Vec<value::Value>,
+			keyword_arguments: Vec<(Identifier, value::Value)>
+		) -> rust::Result {
+			operations::call_method( vm, &self.receiver, self.name, arguments, keyword_arguments )
+		}
+	}
+	
+	impl RefCounted for BuiltinMethod {}
+	impl RefCountedSpecial for BuiltinMethod {}
+
+/// A function implemented in Rust.
+pub struct RustFunction {
+	function: fn ( &mut VirtualMachine, Vec<value::Value>, Vec<(Identifier, value::Value)> ) -> rust::Result,
+}
+
+	impl RustFunction {
+		
+		pub fn new(
+			function: fn ( &mut VirtualMachine, Vec<value::Value>, Vec<(Identifier, value::Value)> ) -> rust::Result
+		) -> RustFunction {
+			RustFunction {
+				function: function,
+			}
+		}
+	}
+	
+	impl Special for RustFunction {
+		fn repr( &self ) -> String { "<Function>".into_string() }
+		fn is_callable( &self ) -> bool { true }
+		
+		fn call(
+			&self,
+			vm: &mut VirtualMachine,
+			arguments: Vec<value::Value>,
+			keyword_arguments: Vec<(Identifier, value::Value)>
+		) -> rust::Result {
+			( self.function )( vm, arguments, keyword_arguments )
+		}
+	}
+	
+	impl RefCounted for RustFunction {}
+	impl RefCountedSpecial for RustFunction {}
@@ -0,0 +1,85 @@
+use std::num::{FromPrimitive, ToPrimitive, Float};
+use std::cmp::{Ordering, Less, Equal, Greater};
+use num::bigint::BigInt;
+use mem::rc::Rc;
+use lang::value;
+
+// Integers are `value::Integer` while they fit in an i64, and `value::BigInteger` otherwise.
+// Every operation on integers returns them in that form, so the two are never equal to each other.
+
+pub fn to_big( i: i64 ) -> BigInt {
+	FromPrimitive::from_i64( i ).unwrap()
+}
+
+/// An integer value as a big integer, or `None` if the value is not an integer.
+pub fn as_big( value: &value::Value ) -> Option<BigInt> {
+	match *value {
+		value::Integer( i ) => Some( to_big( i ) ),
+		value::BigInteger( ref big ) => Some( (**big).clone() ),
+		_ => None,
+	}
+}
+
+/// Wrap a big integer, as a plain `Integer` if it fits.
+pub fn from_big( big: BigInt ) -> value::Value {
+	match big.to_i64() {
+		Some( i ) => value::Integer( i ),
+		None => value::BigInteger( Rc::new( big ) ),
+	}
+}
+
+/// The nearest float, which is infinite if the integer is too large.
+pub fn to_float( big: &BigInt ) -> f64 {
+	from_str::<f64>( big.to_string().as_slice() ).unwrap()
+}
+
+/// The exact value of a finite float without a fractional part.
+pub fn from_integral_float( float: f64 ) -> BigInt {
+	let (mantissa, exponent, sign) = float.integer_decode();
+	let mantissa: BigInt = FromPrimitive::from_u64( mantissa ).unwrap();
+	let magnitude = if exponent >= 0 {
+		mantissa << exponent as uint
+	} else {
+		mantissa >> ( -exponent ) as uint
+	};
+	if sign < 0 { -magnitude } else { magnitude }
+}
+
+/// Compare an integer to a float exactly, rather than rounding the integer to the nearest float.
+///
+/// Returns `None` if the float is NaN.
+pub fn compare_to_float( integer: &BigInt, float: f64 ) -> Option<Ordering> {
+	
+	if float.is_nan() {
+		return None;
+	}
+	
+	if float.is_infinite() {
+		return Some( if float > 0f64 { Less } else { Greater } );
+	}
+	
+	let floor = float.floor();
+	match integer.cmp( &from_integral_float( floor ) ) {
+		Equal => Some( if float > floor { Less } else { Equal } ),
+		ordering @ _ => Some( ordering ),
+	}
+}
+
+/// Like `compare_to_float`, without allocating for integers that convert to floats exactly.
+pub fn compare_small_to_float( integer: i64, float: f64 ) -> Option<Ordering> {
+	
+	if -( 1 << 53 ) <= integer && integer <= 1 << 53 {
+		let integer = integer as f64;
+		return if integer < float {
+			Some( Less )
+		} else if integer > float {
+			Some( Greater )
+		} else if integer == float {
+			Some( Equal )
+		} else {
+			None
+		};
+	}
+	
+	compare_to_float( &to_big( integer ), float )
+}
@@ -0,0 +1,188 @@
+use lang::value;
+use lang::special;
+use lang::special::{Special, RefCountedSpecial};
+use lang::identifier::Identifier;
+use lang::function::Function;
+use lang::object::Object;
+use lang::operations;
+use mem::rc::{Rc, RefCounted};
+use mem::gc::Gc;
+use lang::list::List;
+use lang::map::Map;
+use builtin::burn::errors::create_type_error;
+use vm::virtual_machine::VirtualMachine;
+use vm::run::frame::Frame;
+use vm::run::rust;
+use vm::run::rust::Operation;
+
+/// Iterates over the characters of a string, each yielded as a string of its own.
+pub struct StringIterator {
+	string: Rc<String>,
+	position: uint,
+}
+
+	impl Special for StringIterator {
+		fn repr( &self ) -> String { "<Iterator>".into_string() }
+		fn is_iterator( &self ) -> bool { true }
+		
+		fn next( &mut self ) -> Option<value::Value> {
+			
+			if self.position >= self.string.len() {
+				return None;
+			}
+			
+			let range = self.string.as_slice().char_range_at( self.position );
+			self.position = range.next;
+			
+			let mut character = String::new();
+			character.push_char( range.ch );
+			Some( value::String( Rc::new( character ) ) )
+		}
+	}
+	
+	impl RefCounted for StringIterator {}
+	impl RefCountedSpecial for StringIterator {}
+
+pub fn iterate_string( string: &Rc<String> ) -> value::Value {
+	special::create_rc_value( StringIterator {
+		string: string.clone(),
+		position: 0,
+	} )
+}
+
+/// Iterates over the items of a list.
+///
+/// Items added to the list while iterating are yielded as well.
+pub struct ListIterator {
+	list: Gc<List>,
+	position: uint,
+}
+
+	impl Special for ListIterator {
+		fn repr( &self ) -> String { "<Iterator>".into_string() }
+		fn is_iterator( &self ) -> bool { true }
+		
+		fn next( &mut self ) -> Option<value::Value> {
+			
+			if self.position >= self.list.items.len() {
+				return None;
+			}
+			
+			let item = self.list.items.get( self.position ).clone();
+			self.position += 1;
+			Some( item )
+		}
+	}
+	
+	impl RefCounted for ListIterator {}
+	impl RefCountedSpecial for ListIterator {}
+
+pub fn iterate_list( list: &Gc<List> ) -> value::Value {
+	special::create_rc_value( ListIterator {
+		list: list.clone(),
+		position: 0,
+	} )
+}
+
+/// Iterates over the keys of a map, in the order they were first set.
+pub struct MapIterator {
+	map: Gc<Map>,
+	position: uint,
+}
+
+	impl Special for MapIterator {
+		fn repr( &self ) -> String { "<Iterator>".into_string() }
+		fn is_iterator( &self ) -> bool { true }
+		
+		fn next( &mut self ) -> Option<value::Value> {
+			
+			if self.position >= self.map.entries.len() {
+				return None;
+			}
+			
+			let &(ref key, _) = self.map.entries.get( self.position );
+			self.position += 1;
+			Some( key.clone() )
+		}
+	}
+	
+	impl RefCounted for MapIterator {}
+	impl RefCountedSpecial for MapIterator {}
+
+pub fn iterate_map( map: &Gc<Map> ) -> value::Value {
+	special::create_rc_value( MapIterator {
+		map: map.clone(),
+		position: 0,
+	} )
+}
+
+/// Whether a value is an iterator: a special one, or an object with `next` and `done` methods.
+pub fn is_iterator( value: &value::Value ) -> bool {
+	match *value {
+		value::RcSpecial( ref r ) => r.is_iterator(),
+		value::Object( ref object ) => has_method( object, "next" ) && has_method( object, "done" ),
+		_ => false,
+	}
+}
+
+fn has_method( object: &Gc<Object>, name: &str ) -> bool {
+	object.class.find_method( Identifier::find_or_create( name ) ).is_some()
+}
+
+/// Get the iterator of an object: the object itself if it is an iterator,
+/// or whatever its `iterate` method returns, which has to be an iterator.
+///
+/// `None` means the object is not iterable.
+pub fn iterate_object( value: &value::Value, object: &Gc<Object> ) -> Option<rust::Result> {
+	
+	if has_method( object, "next" ) {
+		if ! has_method( object, "done" ) {
+			return Some( rust::Throw( create_type_error(
+				format!( "{} has a `next` method, but no `done` method", operations::repr( value ) )
+			) ) );
+		}
+		return Some( rust::Ok( value.clone() ) );
+	}
+	
+	match object.class.find_method( Identifier::find_or_create( "iterate" ) ) {
+		Some( iterate ) => Some( rust::Rust( box IterateObject {
+			object: value.clone(),
+			iterate: Some( iterate ),
+		} as Box<rust::Operation> ) ),
+		None => None,
+	}
+}
+
+/// Calls the `iterate` method of an object, and checks that it returned an iterator.
+struct IterateObject {
+	object: value::Value,
+	iterate: Option<Gc<Function>>,
+}
+
+	impl Operation for IterateObject {
+		fn run( &mut self, vm: &mut VirtualMachine, result: Result<value::Value,value::Value> ) -> rust::Result {
+			
+			// the first run calls `iterate`, the second one gets its result
+			match self.iterate.take() {
+				Some( iterate ) => {
+					return match Frame::new_function_call( vm, iterate, Some( self.object.clone() ), Vec::new(), Vec::new() ) {
+						Ok( frame ) => rust::Burn( frame ),
+						Err( throwable ) => rust::Throw( throwable ),
+					};
+				}
+				None => {}
+			}
+			
+			match result {
+				Ok( iterator ) => {
+					if ! is_iterator( &iterator ) {
+						return rust::Throw( create_type_error(
+							format!( "{} is not an iterator", operations::repr( &iterator ) )
+						) );
+					}
+					rust::Ok( iterator )
+				}
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+	}
@@ -0,0 +1,197 @@
+use std::cmp::Equal;
+use std::num::Signed;
+use mem::gc::{Gc, GarbageCollected};
+use lang::value;
+use lang::identifier::Identifier;
+use lang::operations;
+use builtin::burn::errors::{create_type_error, create_index_error, create_overflow_error};
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
+
+pub struct List {
+	#[doc(hidden)]
+	pub items: Vec<value::Value>,
+}
+
+	impl List {
+		
+		pub fn new( items: Vec<value::Value> ) -> List {
+			List {
+				items: items,
+			}
+		}
+		
+		/// Resolve an index, counting from the end if it is negative.
+		///
+		/// Throws an `IndexError` if it is out of bounds.
+		pub fn resolve_index( &self, index: &value::Value ) -> Result<uint,value::Value> {
+			resolve_index( index, "List", self.items.len(), self.items.len() )
+		}
+	}
+	
+	impl GarbageCollected for List {
+		
+		fn mark( &mut self ) {
+			unimplemented!();
+		}
+	}
+
+/// Resolve an index into `0..limit`, for a sequence of the given length, named `kind` in errors.
+/// `limit` is one more than the length where an index past the end is allowed, e.g. for `insert`.
+pub fn resolve_index( index: &value::Value, kind: &str, length: uint, limit: uint ) -> Result<uint,value::Value> {
+
+	let i = match *index {
+		value::Integer( i ) => i,
+		value::BigInteger( ref i ) => {
+			return Err( create_overflow_error(
+				format!( "{} indices must fit in 64 bits, got {}", kind, i.to_string() )
+			) );
+		}
+		_ => {
+			return Err( create_type_error(
+				format!( "{} indices must be Integers, got {}", kind, operations::repr( index ) )
+			) );
+		}
+	};
+	
+	let resolved = if i < 0 { length as i64 + i } else { i };
+	
+	if resolved < 0 || resolved >= limit as i64 {
+		return Err( create_index_error(
+			format!( "Index {} is out of range for a {} of length {}", i, kind, length )
+		) );
+	}
+	
+	Ok( resolved as uint )
+}
+
+/// Clamp a slice bound into `0..length`, counting from the end if it is negative.
+pub fn clamp_bound( bound: &value::Value, kind: &str, length: uint ) -> Result<uint,value::Value> {
+	match *bound {
+		value::Integer( i ) => Ok( clamp_small_bound( i, length ) ),
+		// beyond either end of any sequence
+		value::BigInteger( ref i ) => Ok( if i.is_negative() { 0 } else { length } ),
+		_ => Err( create_type_error(
+			format!( "{} indices must be Integers, got {}", kind, operations::repr( bound ) )
+		) ),
+	}
+}
+
+fn clamp_small_bound( bound: i64, length: uint ) -> uint {
+	let length = length as i64;
+	let resolved = if bound < 0 { length + bound } else { bound };
+	if resolved < 0 { 0 } else if resolved > length { length as uint } else { resolved as uint }
+}
+
+pub fn has_method( name: Identifier ) -> bool {
+	let mut name = name;
+	match name.get_value() {
+		"push" | "pop" | "insert" | "remove" | "slice" | "sort" | "reverse" => true,
+		_ => false,
+	}
+}
+
+pub fn call_method(
+	vm: &mut VirtualMachine,
+	list: &Gc<List>,
+	name: Identifier,
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> rust::Result {
+
+	let mut list = list.clone();
+	let mut method_name = name;
+	
+	match method_name.get_value() {
+		
+		"push" => {
+			let mut arguments = try_arguments!( &["item"], arguments, keyword_arguments );
+			list.items.push( arguments.pop().unwrap() );
+			rust::Ok( value::Nothing )
+		}
+		
+		"pop" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			match list.items.pop() {
+				Some( item ) => rust::Ok( item ),
+				None => rust::Throw( create_index_error( "Can't pop from an empty List".to_string() ) ),
+			}
+		}
+		
+		"insert" => {
+			let mut arguments = try_arguments!( &["index", "item"], arguments, keyword_arguments );
+			let item = arguments.pop().unwrap();
+			let length = list.items.len();
+			match resolve_index( arguments.get( 0 ), "List", length, length + 1 ) {
+				Ok( i ) => {
+					list.items.insert( i, item );
+					rust::Ok( value::Nothing )
+				}
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		"remove" => {
+			let arguments = try_arguments!( &["index"], arguments, keyword_arguments );
+			match list.resolve_index( arguments.get( 0 ) ) {
+				Ok( i ) => rust::Ok( list.items.remove( i ).unwrap() ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		"slice" => {
+			let arguments = try_arguments!( &["start", "end"], arguments, keyword_arguments );
+			let length = list.items.len();
+			
+			let mut bounds = Vec::new();
+			for argument in arguments.iter() {
+				match clamp_bound( argument, "List", length ) {
+					Ok( bound ) => { bounds.push( bound ); }
+					Err( throwable ) => { return rust::Throw( throwable ); }
+				}
+			}
+			
+			let (start, end) = (*bounds.get( 0 ), *bounds.get( 1 ));
+			let items = if start < end {
+				Vec::from_slice( list.items.slice( start, end ) )
+			} else {
+				Vec::new()
+			};
+			
+			rust::Ok( value::List( vm.lists.register( List::new( items ) ) ) )
+		}
+		
+		"sort" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			
+			// the first pair of items that can't be compared makes the sort throw
+			let mut error = None;
+			list.items.sort_by( |a, b| {
+				if error.is_some() {
+					return Equal;
+				}
+				match operations::try_compare( a, b ) {
+					Ok( Some( ordering ) ) => ordering,
+					Ok( None ) => Equal,
+					Err( throwable ) => {
+						error = Some( throwable );
+						Equal
+					}
+				}
+			} );
+			
+			match error {
+				Some( throwable ) => rust::Throw( throwable ),
+				None => rust::Ok( value::Nothing ),
+			}
+		}
+		
+		"reverse" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			list.items.reverse();
+			rust::Ok( value::Nothing )
+		}
+		
+		_ => { unreachable!(); }
+	}
+}
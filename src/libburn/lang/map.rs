@@ -0,0 +1,197 @@
+use std::mem;
+use std::num::Float;
+use std::collections::HashMap;
+use mem::gc::{Gc, GarbageCollected};
+use lang::value;
+use lang::identifier::Identifier;
+use lang::list::List;
+use lang::integer;
+use lang::operations;
+use builtin::burn::errors::{create_type_error, create_index_error};
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
+
+/// A map from hashable values to values, which remembers the order its keys were first set in.
+pub struct Map {
+	indices: HashMap<Key, uint>,
+	#[doc(hidden)]
+	pub entries: Vec<(value::Value, value::Value)>,
+}
+
+	impl Map {
+		
+		pub fn new() -> Map {
+			Map {
+				indices: HashMap::new(),
+				entries: Vec::new(),
+			}
+		}
+		
+		/// Throws a `TypeError` if the key is not hashable.
+		pub fn get( &self, key: &value::Value ) -> Result<Option<value::Value>,value::Value> {
+			let key = try!( Key::new( key ) );
+			match self.indices.find( &key ) {
+				Some( &i ) => {
+					let &(_, ref value) = self.entries.get( i );
+					Ok( Some( value.clone() ) )
+				}
+				None => Ok( None ),
+			}
+		}
+		
+		/// Throws a `TypeError` if the key is not hashable.
+		pub fn set( &mut self, key: value::Value, value: value::Value ) -> Result<(),value::Value> {
+			
+			let hashed = try!( Key::new( &key ) );
+			
+			match self.indices.find( &hashed ) {
+				Some( &i ) => {
+					match *self.entries.get_mut( i ) {
+						(_, ref mut old) => { *old = value; }
+					}
+					return Ok( () );
+				}
+				None => {}
+			}
+			
+			self.indices.insert( hashed, self.entries.len() );
+			self.entries.push( (key, value) );
+			Ok( () )
+		}
+		
+		/// Throws a `TypeError` if the key is not hashable.
+		pub fn remove( &mut self, key: &value::Value ) -> Result<Option<value::Value>,value::Value> {
+			
+			let hashed = try!( Key::new( key ) );
+			
+			let i = match self.indices.pop( &hashed ) {
+				Some( i ) => i,
+				None => { return Ok( None ); }
+			};
+			
+			// keep the order of the remaining entries
+			for (_, index) in self.indices.mut_iter() {
+				if *index > i {
+					*index -= 1;
+				}
+			}
+			
+			let (_, value) = self.entries.remove( i ).unwrap();
+			Ok( Some( value ) )
+		}
+		
+		pub fn len( &self ) -> uint {
+			self.entries.len()
+		}
+	}
+	
+	impl GarbageCollected for Map {
+		
+		fn mark( &mut self ) {
+			unimplemented!();
+		}
+	}
+
+/// The hashable form of a value.
+///
+/// Only immutable values can be hashed. Numbers that are equal hash the same, like `1` and `1.0`.
+#[deriving(PartialEq, Eq, Hash)]
+enum Key {
+	NothingKey,
+	BooleanKey( bool ),
+	IntegerKey( i64 ),
+	BigIntegerKey( String ),
+	FloatKey( u64 ),
+	StringKey( String ),
+}
+
+	impl Key {
+		
+		fn new( value: &value::Value ) -> Result<Key,value::Value> {
+			match *value {
+				
+				value::Nothing => Ok( NothingKey ),
+				value::Boolean( b ) => Ok( BooleanKey( b ) ),
+				value::Integer( i ) => Ok( IntegerKey( i ) ),
+				value::BigInteger( ref i ) => Ok( BigIntegerKey( i.to_string() ) ),
+				value::String( ref s ) => Ok( StringKey( s.as_slice().to_string() ) ),
+				
+				value::Float( f ) => {
+					if ! f.is_finite() || f != f.trunc() {
+						Ok( FloatKey( unsafe { mem::transmute( f ) } ) )
+					} else if f >= -9223372036854775808f64 && f < 9223372036854775808f64 {
+						Ok( IntegerKey( f as i64 ) )
+					} else {
+						Ok( BigIntegerKey( integer::from_integral_float( f ).to_string() ) )
+					}
+				}
+				
+				_ => Err( create_type_error( format!( "{} is not hashable", operations::repr( value ) ) ) ),
+			}
+		}
+	}
+
+pub fn has_method( name: Identifier ) -> bool {
+	let mut name = name;
+	match name.get_value() {
+		"keys" | "values" | "has" | "remove" | "size" => true,
+		_ => false,
+	}
+}
+
+pub fn call_method(
+	vm: &mut VirtualMachine,
+	map: &Gc<Map>,
+	name: Identifier,
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> rust::Result {
+
+	let mut map = map.clone();
+	let mut method_name = name;
+	
+	match method_name.get_value() {
+		
+		"keys" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			let keys = map.entries.iter().map( |&(ref key, _)| { key.clone() } ).collect();
+			rust::Ok( value::List( vm.lists.register( List::new( keys ) ) ) )
+		}
+		
+		"values" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			let values = map.entries.iter().map( |&(_, ref value)| { value.clone() } ).collect();
+			rust::Ok( value::List( vm.lists.register( List::new( values ) ) ) )
+		}
+		
+		"has" => {
+			let arguments = try_arguments!( &["key"], arguments, keyword_arguments );
+			match map.get( arguments.get( 0 ) ) {
+				Ok( value ) => rust::Ok( value::Boolean( value.is_some() ) ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		"remove" => {
+			let arguments = try_arguments!( &["key"], arguments, keyword_arguments );
+			let key = arguments.get( 0 );
+			match map.remove( key ) {
+				Ok( Some( value ) ) => rust::Ok( value ),
+				Ok( None ) => rust::Throw( create_missing_key_error( key ) ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		"size" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			rust::Ok( value::Integer( map.len() as i64 ) )
+		}
+		
+		_ => { unreachable!(); }
+	}
+}
+
+/// The `IndexError` for a key that is not in a map.
+pub fn create_missing_key_error( key: &value::Value ) -> value::Value {
+	create_index_error( format!( "Map has no key {}", operations::item_to_string( key ) ) )
+}
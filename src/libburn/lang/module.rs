@@ -62,6 +62,10 @@ pub struct Module {
 			self.locked = true
 		}
 		
+		pub fn is_locked( &self ) -> bool {
+			self.locked
+		}
+		
 		pub fn find_id( &self, identifier: Identifier ) -> Result<Value, Value> {
 			match self.contents.find( &identifier ) {
 				Some( value ) => Ok( value.clone() ),
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use mem::gc::{Gc, GarbageCollected};
+use lang::value;
+use lang::identifier::Identifier;
+use lang::class::Class;
+use lang::list::List;
+use lang::map::Map;
+use lang::operations;
+use vm::virtual_machine::VirtualMachine;
+
+pub struct Object {
+	#[doc(hidden)]
+	pub class: Gc<Class>,
+	#[doc(hidden)]
+	pub properties: HashMap<Identifier, value::Value>,
+}
+
+	impl Object {
+		
+		pub fn new( vm: &mut VirtualMachine, class: Gc<Class> ) -> Object {
+			
+			let mut properties = HashMap::new();
+			let mut copies = Vec::new();
+			
+			// ancestors first, so that properties of more derived classes override them
+			for ancestor in class.ancestors.iter().rev() {
+				for &(name, ref value) in ancestor.properties.iter() {
+					properties.insert( name, copy_default( vm, value, &mut copies ) );
+				}
+			}
+			
+			for &(name, ref value) in class.properties.iter() {
+				properties.insert( name, copy_default( vm, value, &mut copies ) );
+			}
+			
+			Object {
+				class: class,
+				properties: properties,
+			}
+		}
+	}
+	
+	impl GarbageCollected for Object {
+		
+		fn mark( &mut self ) {
+			unimplemented!();
+		}
+	}
+
+/// Lists and maps in property defaults are copied for every instance, so that instances don't share them.
+/// `copies` pairs the containers copied so far with their copies, which keeps shared and cyclic ones that way.
+fn copy_default( vm: &mut VirtualMachine, value: &value::Value, copies: &mut Vec<(value::Value, value::Value)> ) -> value::Value {
+	
+	for &(ref original, ref copy) in copies.iter() {
+		if operations::is_same_container( original, value ) {
+			return copy.clone();
+		}
+	}
+	
+	match *value {
+		
+		value::List( ref list ) => {
+			let mut copy = vm.lists.register( List::new( Vec::new() ) );
+			copies.push( (value.clone(), value::List( copy.clone() )) );
+			for item in list.items.iter() {
+				let item = copy_default( vm, item, copies );
+				copy.items.push( item );
+			}
+			value::List( copy )
+		}
+		
+		value::Map( ref map ) => {
+			let mut copy = vm.maps.register( Map::new() );
+			copies.push( (value.clone(), value::Map( copy.clone() )) );
+			for &(ref key, ref item) in map.entries.iter() {
+				let item = copy_default( vm, item, copies );
+				// the keys are hashable, since they were in the original
+				let _ = copy.set( key.clone(), item );
+			}
+			value::Map( copy )
+		}
+		
+		_ => value.clone(),
+	}
+}
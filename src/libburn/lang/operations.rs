@@ -1,10 +1,26 @@
+use std::num::{CheckedAdd, CheckedSub, CheckedMul, CheckedDiv};
+use std::cmp::{Ordering, Less, Equal, Greater};
+use num::Integer;
+use num::bigint::BigInt;
 use lang::identifier::Identifier;
 use lang::value;
 use lang::value::Value;
+use lang::function::{BoundMethod, BuiltinMethod};
+use lang::class::Class;
+use lang::iteration;
+use lang::list;
+use lang::map;
+use lang::string;
+use lang::integer;
+use lang::special;
+use lang::type_;
+use lang::type_::TypeTest;
 use builtin::burn;
-use builtin::burn::errors::create_type_error;
+use builtin::burn::errors::{create_type_error, create_attribute_error, create_division_by_zero_error};
 use mem::rc::Rc;
+use mem::gc::Gc;
 use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
 
 pub fn is_truthy( value: &Value ) -> bool {
 	match *value {
@@ -12,15 +28,23 @@ pub fn is_truthy( value: &Value ) -> bool {
 		value::Nothing => false,
 		value::Boolean( b ) => b,
 		value::Integer( i ) => i != 0,
+		value::BigInteger(..) => true,
 		value::Float( f ) => f != 0f64,
 		value::String( ref s ) => s.len() > 0,
 		
 		value::Function(..)
 		| value::TypeUnion(..)
 		| value::TypeIntersection(..)
+		| value::TypeComplement(..)
 		| value::Module(..)
+		| value::Class(..)
+		| value::Object(..)
+		| value::BoundMethod(..)
 		=> true,
 		
+		value::List( ref l ) => l.items.len() > 0,
+		value::Map( ref m ) => m.len() > 0,
+		
 		value::StaticSpecial(..) => true,
 		value::RcSpecial( ref r ) => r.is_truthy(),
 	}
@@ -31,13 +55,18 @@ pub fn repr( value: &Value ) -> String {
 		
 		value::Nothing => "<Nothing>".to_string(),
 		value::Boolean(..) => "<Boolean>".to_string(),
-		value::Integer(..) => "<Integer>".to_string(),
+		value::Integer(..) | value::BigInteger(..) => "<Integer>".to_string(),
 		value::Float(..) => "<Float>".to_string(),
 		value::String(..) => "<String>".to_string(),
 		
 		value::Function(..) => "<Function>".to_string(),
-		value::TypeUnion(..) | value::TypeIntersection(..) => "<Type>".to_string(),
+		value::TypeUnion(..) | value::TypeIntersection(..) | value::TypeComplement(..) => "<Type>".to_string(),
 		value::Module(..) => "<Module>".to_string(),
+		value::Class(..) => "<Class>".to_string(),
+		value::Object(..) => "<Object>".to_string(),
+		value::List(..) => "<List>".to_string(),
+		value::Map(..) => "<Map>".to_string(),
+		value::BoundMethod(..) => "<Function>".to_string(),
 		
 		value::StaticSpecial( special ) => special.repr(),
 		value::RcSpecial( ref r ) => r.repr(),
@@ -45,22 +74,76 @@ pub fn repr( value: &Value ) -> String {
 }
 
 pub fn to_string( value: &Value ) -> rust::Result {
-	rust::Ok( value::String(
-		match *value {
+	rust::Ok( value::String( to_string_visiting( value, &mut Vec::new() ) ) )
+}
+
+/// `visiting` holds the lists and maps being shown, so that one containing itself is shown as `[...]` or `{...}`.
+fn to_string_visiting( value: &Value, visiting: &mut Vec<Value> ) -> Rc<String> {
+	match *value {
+		
+		value::Nothing => Rc::new( "nothing".into_string() ),
+		value::Boolean( true ) => Rc::new( "true".into_string() ),
+		value::Boolean( false ) => Rc::new( "false".into_string() ),
+		value::Integer( i ) => Rc::new( format!( "{}", i ) ),
+		value::BigInteger( ref i ) => Rc::new( i.to_string() ),
+		value::Float( f ) => Rc::new( format!( "{}", f ) ),
+		value::String( ref s ) => s.clone(),
+		
+		value::StaticSpecial( special ) => Rc::new( special.repr() ),
+		value::RcSpecial( ref r ) => Rc::new( r.to_string() ),
+		
+		value::List( ref l ) => {
 			
-			value::Nothing => Rc::new( "nothing".into_string() ),
-			value::Boolean( true ) => Rc::new( "true".into_string() ),
-			value::Boolean( false ) => Rc::new( "false".into_string() ),
-			value::Integer( i ) => Rc::new( format!( "{}", i ) ),
-			value::Float( f ) => Rc::new( format!( "{}", f ) ),
-			value::String( ref s ) => s.clone(),
+			if visiting.iter().any( |v| { is_same_container( v, value ) } ) {
+				return Rc::new( "[...]".into_string() );
+			}
+			
+			visiting.push( value.clone() );
+			let items = l.items.iter().map( |item| { item_to_string_visiting( item, visiting ) } ).collect::<Vec<String>>();
+			visiting.pop();
+			
+			Rc::new( format!( "[{}]", items.connect( ", " ) ) )
+		}
+		
+		value::Map( ref m ) => {
+			
+			if visiting.iter().any( |v| { is_same_container( v, value ) } ) {
+				return Rc::new( "{...}".into_string() );
+			}
 			
-			value::StaticSpecial( special ) => Rc::new( special.repr() ),
-			value::RcSpecial( ref r ) => Rc::new( r.to_string() ),
+			visiting.push( value.clone() );
+			let entries = m.entries.iter().map( |&(ref key, ref value)| {
+				format!( "{} -> {}", item_to_string_visiting( key, visiting ), item_to_string_visiting( value, visiting ) )
+			} ).collect::<Vec<String>>();
+			visiting.pop();
 			
-			_ => { Rc::new( repr( value ) ) }
+			Rc::new( format!( "\\{{}\\}", entries.connect( ", " ) ) )
 		}
-	) )
+		
+		_ => { Rc::new( repr( value ) ) }
+	}
+}
+
+/// Show an item of a list or map the way it would be written, falling back to its repr.
+pub fn item_to_string( item: &Value ) -> String {
+	item_to_string_visiting( item, &mut Vec::new() )
+}
+
+fn item_to_string_visiting( item: &Value, visiting: &mut Vec<Value> ) -> String {
+	match *item {
+		value::String( ref s ) => format!( "\"{}\"", s.as_slice() ),
+		value::Object(..) => repr( item ),
+		_ => to_string_visiting( item, visiting ).as_slice().to_string(),
+	}
+}
+
+/// Whether two values are the same list or map.
+pub fn is_same_container( left: &Value, right: &Value ) -> bool {
+	match (left, right) {
+		(&value::List( ref l ), &value::List( ref r )) => l == r,
+		(&value::Map( ref l ), &value::Map( ref r )) => l == r,
+		_ => false,
+	}
 }
 
 pub fn add( left: &Value, right: &Value ) -> rust::Result {
@@ -68,23 +151,57 @@ pub fn add( left: &Value, right: &Value ) -> rust::Result {
 		
 		value::Integer( l ) => {
 			match *right {
-				value::Integer( r ) => { return rust::Ok( value::Integer( l + r ) ); }
+				value::Integer( r ) => {
+					match l.checked_add( &r ) {
+						Some( i ) => { return rust::Ok( value::Integer( i ) ); }
+						None => {} // promoted below
+					}
+				}
 				value::Float( r ) => { return rust::Ok( value::Float( l as f64 + r ) ); }
 				_ => {}
 			}
 		}
 		
+		value::BigInteger( ref l ) => {
+			match *right {
+				value::Float( r ) => { return rust::Ok( value::Float( integer::to_float( &**l ) + r ) ); }
+				_ => {}
+			}
+		}
+		
 		value::Float( l ) => {
 			match *right {
 				value::Integer( r ) => { return rust::Ok( value::Float( l + r as f64 ) ); }
+				value::BigInteger( ref r ) => { return rust::Ok( value::Float( l + integer::to_float( &**r ) ) ); }
 				value::Float( r ) => { return rust::Ok( value::Float( l + r ) ); }
 				_ => {}
 			}
 		}
 		
+		value::String( ref l ) => {
+			match *right {
+				value::String( ref r ) => {
+					let mut concatenated = String::with_capacity( l.len() + r.len() );
+					concatenated.push_str( l.as_slice() );
+					concatenated.push_str( r.as_slice() );
+					return rust::Ok( value::String( Rc::new( concatenated ) ) );
+				}
+				_ => {}
+			}
+		}
+		
 		_ => {}
 	}
 	
+	match big_operands( left, right ) {
+		Some( (l, r) ) => { return rust::Ok( integer::from_big( l + r ) ); }
+		None => {}
+	}
+	
+	if burn::types::is_type( left ) || burn::types::is_type( right ) {
+		return intersection( left.clone(), right.clone() );
+	}
+	
 	return rust::Throw(
 		create_type_error( format!( "Can't add {} and {}", repr( left ), repr( right ) ) )
 	);
@@ -95,15 +212,28 @@ pub fn subtract( left: &Value, right: &Value ) -> rust::Result {
 		
 		value::Integer( l ) => {
 			match *right {
-				value::Integer( r ) => { return rust::Ok( value::Integer( l - r ) ); }
+				value::Integer( r ) => {
+					match l.checked_sub( &r ) {
+						Some( i ) => { return rust::Ok( value::Integer( i ) ); }
+						None => {} // promoted below
+					}
+				}
 				value::Float( r ) => { return rust::Ok( value::Float( l as f64 - r ) ); }
 				_ => {}
 			}
 		}
 		
+		value::BigInteger( ref l ) => {
+			match *right {
+				value::Float( r ) => { return rust::Ok( value::Float( integer::to_float( &**l ) - r ) ); }
+				_ => {}
+			}
+		}
+		
 		value::Float( l ) => {
 			match *right {
 				value::Integer( r ) => { return rust::Ok( value::Float( l - r as f64 ) ); }
+				value::BigInteger( ref r ) => { return rust::Ok( value::Float( l - integer::to_float( &**r ) ) ); }
 				value::Float( r ) => { return rust::Ok( value::Float( l - r ) ); }
 				_ => {}
 			}
@@ -112,32 +242,231 @@ pub fn subtract( left: &Value, right: &Value ) -> rust::Result {
 		_ => {}
 	}
 	
+	match big_operands( left, right ) {
+		Some( (l, r) ) => { return rust::Ok( integer::from_big( l - r ) ); }
+		None => {}
+	}
+	
 	return rust::Throw(
 		create_type_error( format!( "Can't subtract {} and {}", repr( left ), repr( right ) ) )
 	);
 }
 
 pub fn multiply( left: &Value, right: &Value ) -> rust::Result {
+	match *left {
+		
+		value::Integer( l ) => {
+			match *right {
+				value::Integer( r ) => {
+					match l.checked_mul( &r ) {
+						Some( i ) => { return rust::Ok( value::Integer( i ) ); }
+						None => {} // promoted below
+					}
+				}
+				value::Float( r ) => { return rust::Ok( value::Float( l as f64 * r ) ); }
+				_ => {}
+			}
+		}
+		
+		value::BigInteger( ref l ) => {
+			match *right {
+				value::Float( r ) => { return rust::Ok( value::Float( integer::to_float( &**l ) * r ) ); }
+				_ => {}
+			}
+		}
+		
+		value::Float( l ) => {
+			match *right {
+				value::Integer( r ) => { return rust::Ok( value::Float( l * r as f64 ) ); }
+				value::BigInteger( ref r ) => { return rust::Ok( value::Float( l * integer::to_float( &**r ) ) ); }
+				value::Float( r ) => { return rust::Ok( value::Float( l * r ) ); }
+				_ => {}
+			}
+		}
+		
+		_ => {}
+	}
+	
+	match big_operands( left, right ) {
+		Some( (l, r) ) => { return rust::Ok( integer::from_big( l * r ) ); }
+		None => {}
+	}
+	
 	return rust::Throw(
 		create_type_error( format!( "Can't multiply {} and {}", repr( left ), repr( right ) ) )
 	);
 }
 
+/// Integer division rounds down, so that `(a / b) * b + a % b == a`.
 pub fn divide( left: &Value, right: &Value ) -> rust::Result {
+	
+	if is_zero( right ) && is_number( left ) {
+		return rust::Throw( create_division_by_zero_error( "Division by zero".to_string() ) );
+	}
+	
+	match *left {
+		
+		value::Integer( l ) => {
+			match *right {
+				value::Integer( r ) => {
+					match floor_divide( l, r ) {
+						Some( i ) => { return rust::Ok( value::Integer( i ) ); }
+						None => {} // promoted below
+					}
+				}
+				value::Float( r ) => { return rust::Ok( value::Float( l as f64 / r ) ); }
+				_ => {}
+			}
+		}
+		
+		value::BigInteger( ref l ) => {
+			match *right {
+				value::Float( r ) => { return rust::Ok( value::Float( integer::to_float( &**l ) / r ) ); }
+				_ => {}
+			}
+		}
+		
+		value::Float( l ) => {
+			match *right {
+				value::Integer( r ) => { return rust::Ok( value::Float( l / r as f64 ) ); }
+				value::BigInteger( ref r ) => { return rust::Ok( value::Float( l / integer::to_float( &**r ) ) ); }
+				value::Float( r ) => { return rust::Ok( value::Float( l / r ) ); }
+				_ => {}
+			}
+		}
+		
+		_ => {}
+	}
+	
+	match big_operands( left, right ) {
+		Some( (l, r) ) => { return rust::Ok( integer::from_big( l.div_floor( &r ) ) ); }
+		None => {}
+	}
+	
 	return rust::Throw(
 		create_type_error( format!( "Can't divide {} and {}", repr( left ), repr( right ) ) )
 	);
 }
 
+/// The result of modulo has the same sign as the right operand.
+pub fn modulo( left: &Value, right: &Value ) -> rust::Result {
+	
+	if is_zero( right ) && is_number( left ) {
+		return rust::Throw( create_division_by_zero_error( "Modulo by zero".to_string() ) );
+	}
+	
+	match *left {
+		
+		value::Integer( l ) => {
+			match *right {
+				value::Integer( r ) => { return rust::Ok( value::Integer( floor_modulo( l, r ) ) ); }
+				value::Float( r ) => { return rust::Ok( value::Float( float_floor_modulo( l as f64, r ) ) ); }
+				_ => {}
+			}
+		}
+		
+		value::BigInteger( ref l ) => {
+			match *right {
+				value::Float( r ) => { return rust::Ok( value::Float( float_floor_modulo( integer::to_float( &**l ), r ) ) ); }
+				_ => {}
+			}
+		}
+		
+		value::Float( l ) => {
+			match *right {
+				value::Integer( r ) => { return rust::Ok( value::Float( float_floor_modulo( l, r as f64 ) ) ); }
+				value::BigInteger( ref r ) => { return rust::Ok( value::Float( float_floor_modulo( l, integer::to_float( &**r ) ) ) ); }
+				value::Float( r ) => { return rust::Ok( value::Float( float_floor_modulo( l, r ) ) ); }
+				_ => {}
+			}
+		}
+		
+		_ => {}
+	}
+	
+	match big_operands( left, right ) {
+		Some( (l, r) ) => { return rust::Ok( integer::from_big( l.mod_floor( &r ) ) ); }
+		None => {}
+	}
+	
+	return rust::Throw(
+		create_type_error( format!( "Can't take modulo of {} and {}", repr( left ), repr( right ) ) )
+	);
+}
+
+fn is_number( value: &Value ) -> bool {
+	match *value {
+		value::Integer(..) | value::BigInteger(..) | value::Float(..) => true,
+		_ => false,
+	}
+}
+
+// big integers are never zero, since zero fits in an i64
+fn is_zero( value: &Value ) -> bool {
+	match *value {
+		value::Integer( i ) => i == 0,
+		value::Float( f ) => f == 0f64,
+		_ => false,
+	}
+}
+
+/// Both operands as big integers, if they are both integers.
+///
+/// Operations on two `Integer`s only get here if their result overflowed an i64.
+fn big_operands( left: &Value, right: &Value ) -> Option<(BigInt, BigInt)> {
+	match (integer::as_big( left ), integer::as_big( right )) {
+		(Some( l ), Some( r )) => Some( (l, r) ),
+		_ => None,
+	}
+}
+
+fn floor_divide( l: i64, r: i64 ) -> Option<i64> {
+	// only fails for the smallest integer divided by -1
+	let quotient = match l.checked_div( &r ) {
+		Some( quotient ) => quotient,
+		None => { return None; }
+	};
+	
+	if l % r != 0 && ( l < 0 ) != ( r < 0 ) {
+		Some( quotient - 1 )
+	} else {
+		Some( quotient )
+	}
+}
+
+fn floor_modulo( l: i64, r: i64 ) -> i64 {
+	
+	// the remainder is zero anyway, but computing it would overflow for the smallest integer
+	if r == -1 {
+		return 0;
+	}
+	
+	let remainder = l % r;
+	if remainder != 0 && ( remainder < 0 ) != ( r < 0 ) {
+		remainder + r
+	} else {
+		remainder
+	}
+}
+
+fn float_floor_modulo( l: f64, r: f64 ) -> f64 {
+	let remainder = l % r;
+	if remainder != 0f64 && ( remainder < 0f64 ) != ( r < 0f64 ) {
+		remainder + r
+	} else {
+		remainder
+	}
+}
+
 pub fn union( left: Value, right: Value ) -> rust::Result {
 	
-	if ! burn::types::is_type( &left ) {
+	if ! is_type_operand( &left ) {
 		return rust::Throw(
 			create_type_error( format!( "Can't create type union: {} is not a type", repr( &left ) ) )
 		);
 	}
 	
-	if ! burn::types::is_type( &right ) {
+	if ! is_type_operand( &right ) {
 		return rust::Throw(
 			create_type_error( format!( "Can't create type union: {} is not a type", repr( &right ) ) )
 		);
@@ -146,78 +475,617 @@ pub fn union( left: Value, right: Value ) -> rust::Result {
 	rust::Ok( value::TypeUnion( Rc::new( ::lang::type_::TypeUnion::new( left, right ) ) ) )
 }
 
+pub fn intersection( left: Value, right: Value ) -> rust::Result {
+	
+	if ! is_type_operand( &left ) {
+		return rust::Throw(
+			create_type_error( format!( "Can't create type intersection: {} is not a type", repr( &left ) ) )
+		);
+	}
+	
+	if ! is_type_operand( &right ) {
+		return rust::Throw(
+			create_type_error( format!( "Can't create type intersection: {} is not a type", repr( &right ) ) )
+		);
+	}
+	
+	rust::Ok( value::TypeIntersection( Rc::new( ::lang::type_::TypeIntersection::new( left, right ) ) ) )
+}
+
+/// Predicate functions are only types where a type is expected,
+/// so they can be combined with types, but `$f + $g` is not an intersection.
+fn is_type_operand( value: &Value ) -> bool {
+	match *value {
+		value::Function(..) => true,
+		_ => burn::types::is_type( value ),
+	}
+}
+
+/// `not` negates truthiness, except for types, where it creates their complement.
+pub fn not( value: &Value ) -> rust::Result {
+	
+	if burn::types::is_type( value ) {
+		return rust::Ok( value::TypeComplement( Rc::new( ::lang::type_::TypeComplement::new( value.clone() ) ) ) );
+	}
+	
+	rust::Ok( value::Boolean( ! is_truthy( value ) ) )
+}
+
 pub fn is( value: &Value, type_: &Value ) -> rust::Result {
-	match *type_ {
+	
+	let mut test = box TypeTest::new( value.clone() );
+	
+	match test.advance( type_::Testing( type_.clone() ) ) {
+		Some( result ) => result,
+		None => rust::Rust( test as Box<rust::Operation> ),
+	}
+}
+
+pub fn eq( left: &Value, right: &Value ) -> rust::Result {
+	rust::Ok( value::Boolean( equals( left, right ) ) )
+}
+
+pub fn neq( left: &Value, right: &Value ) -> rust::Result {
+	rust::Ok( value::Boolean( ! equals( left, right ) ) )
+}
+
+pub fn lt( left: &Value, right: &Value ) -> rust::Result {
+	match try_compare( left, right ) {
+		Ok( ordering ) => rust::Ok( value::Boolean( ordering == Some( Less ) ) ),
+		Err( t ) => rust::Throw( t ),
+	}
+}
+
+pub fn gt( left: &Value, right: &Value ) -> rust::Result {
+	match try_compare( left, right ) {
+		Ok( ordering ) => rust::Ok( value::Boolean( ordering == Some( Greater ) ) ),
+		Err( t ) => rust::Throw( t ),
+	}
+}
+
+pub fn lt_eq( left: &Value, right: &Value ) -> rust::Result {
+	match try_compare( left, right ) {
+		Ok( ordering ) => rust::Ok( value::Boolean( ordering == Some( Less ) || ordering == Some( Equal ) ) ),
+		Err( t ) => rust::Throw( t ),
+	}
+}
+
+pub fn gt_eq( left: &Value, right: &Value ) -> rust::Result {
+	match try_compare( left, right ) {
+		Ok( ordering ) => rust::Ok( value::Boolean( ordering == Some( Greater ) || ordering == Some( Equal ) ) ),
+		Err( t ) => rust::Throw( t ),
+	}
+}
+
+/// Structural equality for simple values, identity for everything else.
+/// Values of different types are never equal, except for integers and floats.
+pub fn equals( left: &Value, right: &Value ) -> bool {
+	equals_visiting( left, right, &mut Vec::new() )
+}
+
+/// `visiting` holds the pairs of lists and maps being compared.
+/// Comparing a pair again means it contains itself, and it is equal unless some other item differs.
+fn equals_visiting( left: &Value, right: &Value, visiting: &mut Vec<(Value, Value)> ) -> bool {
+	
+	// specials decide for themselves, on either side
+	match (left, right) {
 		
-		value::TypeUnion( ref r ) => {
-			return match is( value, &r.left ) {
-				rust::Ok( value::Boolean( false ) ) => is( value, &r.right ),
-				other_result @ _ => other_result,
+		(&value::RcSpecial( ref l ), _) => {
+			match l.equals( right ) {
+				Some( result ) => { return result; }
+				None => {}
 			}
+			return match *right {
+				value::RcSpecial( ref r ) => r.equals( left ).unwrap_or( l == r ),
+				_ => false,
+			};
 		}
 		
-		value::StaticSpecial( special ) => {
-			if special.is_type() {
-				return rust::Ok( value::Boolean( special.type_test( value ) ) )
+		(_, &value::RcSpecial( ref r )) => {
+			return r.equals( left ).unwrap_or( false );
+		}
+		
+		_ => {}
+	}
+	
+	match *left {
+		
+		value::Nothing => {
+			match *right {
+				value::Nothing => true,
+				_ => false,
 			}
 		}
 		
-		value::RcSpecial( ref r ) => {
-			if r.is_type() {
-				return rust::Ok( value::Boolean( r.type_test( value ) ) )
+		value::Boolean( l ) => {
+			match *right {
+				value::Boolean( r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::Integer( l ) => {
+			match *right {
+				value::Integer( r ) => l == r,
+				value::Float( r ) => integer::compare_small_to_float( l, r ) == Some( Equal ),
+				_ => false,
+			}
+		}
+		
+		value::BigInteger( ref l ) => {
+			match *right {
+				value::BigInteger( ref r ) => **l == **r,
+				value::Float( r ) => integer::compare_to_float( &**l, r ) == Some( Equal ),
+				_ => false,
+			}
+		}
+		
+		value::Float( l ) => {
+			match *right {
+				value::Integer( r ) => integer::compare_small_to_float( r, l ) == Some( Equal ),
+				value::BigInteger( ref r ) => integer::compare_to_float( &**r, l ) == Some( Equal ),
+				value::Float( r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::String( ref l ) => {
+			match *right {
+				value::String( ref r ) => l.as_slice() == r.as_slice(),
+				_ => false,
+			}
+		}
+		
+		value::Function( ref l ) => {
+			match *right {
+				value::Function( ref r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::TypeUnion( ref l ) => {
+			match *right {
+				value::TypeUnion( ref r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::TypeIntersection( ref l ) => {
+			match *right {
+				value::TypeIntersection( ref r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::TypeComplement( ref l ) => {
+			match *right {
+				value::TypeComplement( ref r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::Module( l ) => {
+			match *right {
+				value::Module( r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::Class( ref l ) => {
+			match *right {
+				value::Class( ref r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::Object( ref l ) => {
+			match *right {
+				value::Object( ref r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::List( ref l ) => {
+			match *right {
+				value::List( ref r ) => {
+					
+					if l == r || is_visiting_pair( visiting, left, right ) {
+						return true;
+					}
+					
+					if l.items.len() != r.items.len() {
+						return false;
+					}
+					
+					visiting.push( (left.clone(), right.clone()) );
+					let equal = l.items.iter().zip( r.items.iter() ).all( |(a, b)| { equals_visiting( a, b, visiting ) } );
+					visiting.pop();
+					equal
+				}
+				_ => false,
+			}
+		}
+		
+		value::Map( ref l ) => {
+			match *right {
+				value::Map( ref r ) => {
+					
+					if l == r || is_visiting_pair( visiting, left, right ) {
+						return true;
+					}
+					
+					if l.len() != r.len() {
+						return false;
+					}
+					
+					visiting.push( (left.clone(), right.clone()) );
+					let equal = l.entries.iter().all( |&(ref key, ref value)| {
+						match r.get( key ) {
+							Ok( Some( ref other ) ) => equals_visiting( value, other, visiting ),
+							_ => false,
+						}
+					} );
+					visiting.pop();
+					equal
+				}
+				_ => false,
+			}
+		}
+		
+		value::BoundMethod( ref l ) => {
+			match *right {
+				value::BoundMethod( ref r ) => {
+					l.function == r.function && equals_visiting( &l.receiver, &r.receiver, visiting )
+				}
+				_ => false,
+			}
+		}
+		
+		value::StaticSpecial( l ) => {
+			match *right {
+				value::StaticSpecial( r ) => l == r,
+				_ => false,
+			}
+		}
+		
+		value::RcSpecial(..) => { unreachable!(); }
+	}
+}
+
+fn is_visiting_pair( visiting: &Vec<(Value, Value)>, left: &Value, right: &Value ) -> bool {
+	visiting.iter().any( |&(ref l, ref r)| { is_same_container( l, left ) && is_same_container( r, right ) } )
+}
+
+/// Order numbers by value and strings lexicographically.
+///
+/// Returns `None` for values that are comparable but unordered, like NaN.
+pub fn try_compare( left: &Value, right: &Value ) -> Result<Option<Ordering>,Value> {
+	match *left {
+		
+		value::Integer( l ) => {
+			match *right {
+				value::Integer( r ) => { return Ok( Some( l.cmp( &r ) ) ); }
+				value::BigInteger( ref r ) => { return Ok( Some( integer::to_big( l ).cmp( &**r ) ) ); }
+				value::Float( r ) => { return Ok( integer::compare_small_to_float( l, r ) ); }
+				_ => {}
+			}
+		}
+		
+		value::BigInteger( ref l ) => {
+			match *right {
+				value::Integer( r ) => { return Ok( Some( (**l).cmp( &integer::to_big( r ) ) ) ); }
+				value::BigInteger( ref r ) => { return Ok( Some( (**l).cmp( &**r ) ) ); }
+				value::Float( r ) => { return Ok( integer::compare_to_float( &**l, r ) ); }
+				_ => {}
+			}
+		}
+		
+		value::Float( l ) => {
+			match *right {
+				value::Integer( r ) => { return Ok( integer::compare_small_to_float( r, l ).map( reverse ) ); }
+				value::BigInteger( ref r ) => { return Ok( integer::compare_to_float( &**r, l ).map( reverse ) ); }
+				value::Float( r ) => { return Ok( compare_floats( l, r ) ); }
+				_ => {}
+			}
+		}
+		
+		value::String( ref l ) => {
+			match *right {
+				value::String( ref r ) => { return Ok( Some( l.as_slice().cmp( &r.as_slice() ) ) ); }
+				_ => {}
+			}
+		}
+		
+		value::RcSpecial( ref l ) => {
+			match l.compare( right ) {
+				Some( ordering ) => { return Ok( Some( ordering ) ); }
+				None => {}
 			}
 		}
 		
 		_ => {}
 	}
 	
-	return rust::Throw(
-		create_type_error( format!( "{} is not a type", repr( type_ ) ) )
-	);
+	match *right {
+		value::RcSpecial( ref r ) => {
+			match r.compare( left ) {
+				Some( ordering ) => { return Ok( Some( reverse( ordering ) ) ); }
+				None => {}
+			}
+		}
+		_ => {}
+	}
+	
+	Err( create_type_error( format!( "Can't compare {} and {}", repr( left ), repr( right ) ) ) )
 }
 
-pub fn eq( left: &Value, right: &Value ) -> rust::Result {
-	return rust::Throw(
-		create_type_error( format!( "Can't compare {} and {}", repr( left ), repr( right ) ) )
-	); 
+fn reverse( ordering: Ordering ) -> Ordering {
+	match ordering {
+		Less => Greater,
+		Equal => Equal,
+		Greater => Less,
+	}
 }
 
-pub fn neq( left: &Value, right: &Value ) -> rust::Result {
-	return rust::Throw(
-		create_type_error( format!( "Can't compare {} and {}", repr( left ), repr( right ) ) )
-	); 
+fn compare_floats( l: f64, r: f64 ) -> Option<Ordering> {
+	if l < r {
+		Some( Less )
+	} else if l > r {
+		Some( Greater )
+	} else if l == r {
+		Some( Equal )
+	} else {
+		None
+	}
 }
 
-pub fn lt( left: &Value, right: &Value ) -> rust::Result {
+pub fn get_property( accessed: &Value, name: Identifier ) -> rust::Result {
+	match *accessed {
+		
+		value::Object( ref object ) => {
+			
+			match object.properties.find( &name ) {
+				Some( value ) => { return rust::Ok( value.clone() ); }
+				None => {}
+			}
+			
+			match object.class.find_method( name ) {
+				Some( method ) => {
+					let bound_method = BoundMethod::new( accessed.clone(), method );
+					return rust::Ok( value::BoundMethod( Rc::new( bound_method ) ) );
+				}
+				None => {}
+			}
+		}
+		
+		value::Module( module ) => {
+			match module.find_id( name ) {
+				Ok( value ) => { return rust::Ok( value ); }
+				Err( _ ) => {}
+			}
+		}
+		
+		value::StaticSpecial( special ) => {
+			if special.has_method( name ) {
+				return rust::Ok( special::create_rc_value( BuiltinMethod::new( accessed.clone(), name ) ) );
+			}
+		}
+		
+		value::String(..) => {
+			if string::has_method( name ) {
+				return rust::Ok( special::create_rc_value( BuiltinMethod::new( accessed.clone(), name ) ) );
+			}
+		}
+		
+		value::List(..) => {
+			if list::has_method( name ) {
+				return rust::Ok( special::create_rc_value( BuiltinMethod::new( accessed.clone(), name ) ) );
+			}
+		}
+		
+		value::Map(..) => {
+			if map::has_method( name ) {
+				return rust::Ok( special::create_rc_value( BuiltinMethod::new( accessed.clone(), name ) ) );
+			}
+		}
+		
+		value::RcSpecial( ref r ) => {
+			
+			match r.get_property( name ) {
+				Some( value ) => { return rust::Ok( value ); }
+				None => {}
+			}
+			
+			if r.has_method( name ) {
+				return rust::Ok( special::create_rc_value( BuiltinMethod::new( accessed.clone(), name ) ) );
+			}
+		}
+		
+		_ => {}
+	}
+	
 	return rust::Throw(
-		create_type_error( format!( "Can't compare {} and {}", repr( left ), repr( right ) ) )
-	); 
+		create_attribute_error( format!( "{} has no property `{}`", repr( accessed ), name ) )
+	);
 }
 
-pub fn gt( left: &Value, right: &Value ) -> rust::Result {
-	return rust::Throw(
-		create_type_error( format!( "Can't compare {} and {}", repr( left ), repr( right ) ) )
-	); 
+/// Call a method of a built-in value.
+pub fn call_method(
+	vm: &mut VirtualMachine,
+	receiver: &Value,
+	name: Identifier,
+	arguments: Vec<Value>,
+	keyword_arguments: Vec<(Identifier, Value)>
+) -> rust::Result {
+	match *receiver {
+		value::StaticSpecial( special ) => special.call_method( vm, name, arguments, keyword_arguments ),
+		value::RcSpecial( ref r ) => r.call_method( vm, name, arguments, keyword_arguments ),
+		value::String( ref s ) => string::call_method( vm, s, name, arguments, keyword_arguments ),
+		value::List( ref l ) => list::call_method( vm, l, name, arguments, keyword_arguments ),
+		value::Map( ref m ) => map::call_method( vm, m, name, arguments, keyword_arguments ),
+		_ => { unreachable!(); }
+	}
 }
 
-pub fn lt_eq( left: &Value, right: &Value ) -> rust::Result {
-	return rust::Throw(
-		create_type_error( format!( "Can't compare {} and {}", repr( left ), repr( right ) ) )
-	); 
+pub fn get_item( accessed: &Value, key: &Value ) -> rust::Result {
+	match *accessed {
+		
+		value::String( ref s ) => {
+			match string::get_character( s.as_slice(), key ) {
+				Ok( character ) => rust::Ok( value::String( Rc::new( character ) ) ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		value::List( ref l ) => {
+			match l.resolve_index( key ) {
+				Ok( i ) => rust::Ok( l.items.get( i ).clone() ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		value::Map( ref m ) => {
+			match m.get( key ) {
+				Ok( Some( value ) ) => rust::Ok( value ),
+				Ok( None ) => rust::Throw( map::create_missing_key_error( key ) ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		_ => rust::Throw(
+			create_type_error( format!( "Can't get an item of {}", repr( accessed ) ) )
+		),
+	}
 }
 
-pub fn gt_eq( left: &Value, right: &Value ) -> rust::Result {
+pub fn set_item( accessed: &Value, key: &Value, value: &Value ) -> rust::Result {
+	match *accessed {
+		
+		value::List( ref l ) => {
+			let mut l = l.clone();
+			match l.resolve_index( key ) {
+				Ok( i ) => {
+					*l.items.get_mut( i ) = value.clone();
+					rust::Ok( value::Nothing )
+				}
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		value::Map( ref m ) => {
+			let mut m = m.clone();
+			match m.set( key.clone(), value.clone() ) {
+				Ok( () ) => rust::Ok( value::Nothing ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		_ => rust::Throw(
+			create_type_error( format!( "Can't set an item of {}", repr( accessed ) ) )
+		),
+	}
+}
+
+/// Look up a method the way `super.name` does inside a method of `owner`.
+pub fn get_super_property( this: &Value, owner: &Gc<Class>, name: Identifier ) -> rust::Result {
+	match *this {
+		
+		value::Object( ref object ) => {
+			
+			match object.class.find_next_method( owner, name ) {
+				Some( method ) => {
+					let bound_method = BoundMethod::new( this.clone(), method );
+					return rust::Ok( value::BoundMethod( Rc::new( bound_method ) ) );
+				}
+				None => {}
+			}
+		}
+		
+		_ => { unreachable!(); }
+	}
+	
 	return rust::Throw(
-		create_type_error( format!( "Can't compare {} and {}", repr( left ), repr( right ) ) )
-	); 
+		create_attribute_error( format!( "super has no property `{}`", name ) )
+	);
 }
 
-pub fn get_property( accessed: &Value, name: Identifier ) -> rust::Result {
-	(accessed); (name);
-	unimplemented!();
+/// Get an iterator for anything but objects, whose `iterate` method has to be called by the VM.
+pub fn iterate( value: &Value ) -> rust::Result {
+	match *value {
+		
+		value::String( ref string ) => {
+			return rust::Ok( iteration::iterate_string( string ) );
+		}
+		
+		value::List( ref list ) => {
+			return rust::Ok( iteration::iterate_list( list ) );
+		}
+		
+		value::Map( ref map ) => {
+			return rust::Ok( iteration::iterate_map( map ) );
+		}
+		
+		value::RcSpecial( ref r ) => {
+			
+			if r.is_iterator() {
+				return rust::Ok( value.clone() );
+			}
+			
+			match r.iterate() {
+				Some( iterator ) => {
+					if ! iteration::is_iterator( &iterator ) {
+						return rust::Throw( create_type_error(
+							format!( "{} is not an iterator", repr( &iterator ) )
+						) );
+					}
+					return rust::Ok( iterator );
+				}
+				None => {}
+			}
+		}
+		
+		value::Object( ref object ) => {
+			match iteration::iterate_object( value, object ) {
+				Some( result ) => { return result; }
+				None => {}
+			}
+		}
+		
+		_ => {}
+	}
+	
+	return rust::Throw(
+		create_type_error( format!( "{} is not iterable", repr( value ) ) )
+	);
 }
 
 pub fn set_property( accessed: &Value, name: Identifier, value: &Value ) -> rust::Result {
-	(accessed); (name); (value);
-	unimplemented!();
+	match *accessed {
+		
+		value::Object( ref object ) => {
+			let mut object = object.clone();
+			object.properties.insert( name, value.clone() );
+			return rust::Ok( value::Nothing );
+		}
+		
+		value::Module( mut module ) => {
+			if ! module.is_locked() {
+				module.add_with_id( name, value.clone() );
+				return rust::Ok( value::Nothing );
+			}
+		}
+		
+		value::RcSpecial( ref r ) => {
+			let mut r = r.clone();
+			if r.set_property( name, value.clone() ) {
+				return rust::Ok( value::Nothing );
+			}
+		}
+		
+		_ => {}
+	}
+	
+	return rust::Throw(
+		create_attribute_error( format!( "Can't set property `{}` of {}", name, repr( accessed ) ) )
+	);
 }
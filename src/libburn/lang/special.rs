@@ -1,6 +1,9 @@
+use std::cmp::Ordering;
 use lang::value;
 use lang::identifier::Identifier;
 use mem::rc::{Rc, RefCounted};
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
 
 // todo! rust results
 
@@ -11,6 +14,28 @@ pub trait Special {
 	fn is_type( &self ) -> bool { false }
 	fn type_test( &self, &value::Value ) -> bool { unreachable!() }
 	fn is_throwable( &self ) -> bool { false }
+	/// `None` falls back to identity.
+	fn equals( &self, &value::Value ) -> Option<bool> { None }
+	/// `None` means the values can't be compared.
+	fn compare( &self, &value::Value ) -> Option<Ordering> { None }
+	fn iterate( &self ) -> Option<value::Value> { None }
+	fn is_iterator( &self ) -> bool { false }
+	fn next( &mut self ) -> Option<value::Value> { unreachable!() }
+	fn is_callable( &self ) -> bool { false }
+	fn call( &self, &mut VirtualMachine, Vec<value::Value>, Vec<(Identifier, value::Value)> ) -> rust::Result { unreachable!() }
+	/// `None` means there is no such property.
+	fn get_property( &self, Identifier ) -> Option<value::Value> { None }
+	/// `false` means the property can't be set.
+	fn set_property( &mut self, Identifier, value::Value ) -> bool { false }
+	/// Methods are looked up as properties, which bind them to the special.
+	fn has_method( &self, Identifier ) -> bool { false }
+	fn call_method(
+		&self,
+		&mut VirtualMachine,
+		Identifier,
+		Vec<value::Value>,
+		Vec<(Identifier, value::Value)>
+	) -> rust::Result { unreachable!() }
 }
 
 pub trait RefCountedSpecial : Special + RefCounted {}
@@ -51,11 +76,25 @@ pub fn create_rc_value<T:RefCountedSpecial+'static>( special: T ) -> value::Valu
 
 
 pub fn static_has_no_methods( _: Identifier ) -> bool { false }
+pub fn static_call_no_methods(
+	_: StaticSpecial,
+	_: &mut VirtualMachine,
+	_: Identifier,
+	_: Vec<value::Value>,
+	_: Vec<(Identifier, value::Value)>
+) -> rust::Result { unreachable!() }
 pub fn static_not_a_type( _: &value::Value ) -> bool { unreachable!() }
 
 pub struct StaticSpecialDef {
 	pub repr: &'static str,
 	pub has_method: fn ( Identifier ) -> bool,
+	pub call_method: fn (
+		StaticSpecial,
+		&mut VirtualMachine,
+		Identifier,
+		Vec<value::Value>,
+		Vec<(Identifier, value::Value)>
+	) -> rust::Result,
 	pub type_test: fn ( &value::Value ) -> bool,
 }
 
@@ -64,6 +103,12 @@ pub struct StaticSpecial {
 	def: &'static StaticSpecialDef,
 }
 
+	impl PartialEq for StaticSpecial {
+		fn eq( &self, other: &StaticSpecial ) -> bool {
+			self.def as *StaticSpecialDef == other.def as *StaticSpecialDef
+		}
+	}
+	
 	impl StaticSpecial {
 		
 		pub fn new( def: &'static StaticSpecialDef ) -> StaticSpecial {
@@ -75,4 +120,14 @@ pub struct StaticSpecial {
 		pub fn is_type( self ) -> bool { &self.def.type_test as *_ != &static_not_a_type as *_ }
 		pub fn type_test( self, value: &value::Value ) -> bool { ( self.def.type_test )( value ) }
 		pub fn is_throwable( self ) -> bool { false }
+		pub fn has_method( self, name: Identifier ) -> bool { ( self.def.has_method )( name ) }
+		pub fn call_method(
+			self,
+			vm: &mut VirtualMachine,
+			name: Identifier,
+			arguments: Vec<value::Value>,
+			keyword_arguments: Vec<(Identifier, value::Value)>
+		) -> rust::Result {
+			( self.def.call_method )( self, vm, name, arguments, keyword_arguments )
+		}
 	}
@@ -0,0 +1,214 @@
+use mem::rc::Rc;
+use lang::value;
+use lang::identifier::Identifier;
+use lang::list;
+use lang::list::List;
+use lang::operations;
+use builtin::burn::errors::create_argument_error;
+use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
+
+// All indices into strings count characters, not bytes.
+
+/// Get the character at an index, counting from the end if it is negative.
+///
+/// Throws an `IndexError` if it is out of bounds.
+pub fn get_character( string: &str, index: &value::Value ) -> Result<String,value::Value> {
+	let i = try!( list::resolve_index( index, "String", string.char_len(), string.char_len() ) );
+	let mut character = String::new();
+	character.push_char( string.chars().nth( i ).unwrap() );
+	Ok( character )
+}
+
+/// The byte offset of the character at `index`, or the length of the string if it is past the end.
+fn byte_offset( string: &str, index: uint ) -> uint {
+	match string.char_indices().nth( index ) {
+		Some( (offset, _) ) => offset,
+		None => string.len(),
+	}
+}
+
+/// Join the items of a list of strings, with `separator` between them.
+///
+/// Throws an `ArgumentError` if `items` is not a list of strings.
+pub fn join( separator: &str, items: &value::Value ) -> Result<String,value::Value> {
+
+	let list = match *items {
+		value::List( ref l ) => l,
+		_ => {
+			return Err( create_argument_error(
+				format!( "`join` expects a List, got {}", operations::repr( items ) )
+			) );
+		}
+	};
+	
+	let mut joined = String::new();
+	
+	for (i, item) in list.items.iter().enumerate() {
+		match *item {
+			value::String( ref s ) => {
+				if i > 0 {
+					joined.push_str( separator );
+				}
+				joined.push_str( s.as_slice() );
+			}
+			_ => {
+				return Err( create_argument_error(
+					format!( "`join` expects a List of Strings, got {}", operations::repr( item ) )
+				) );
+			}
+		}
+	}
+	
+	Ok( joined )
+}
+
+fn expect_string( method_name: &str, argument: &value::Value ) -> Result<Rc<String>,value::Value> {
+	match *argument {
+		value::String( ref s ) => Ok( s.clone() ),
+		_ => Err( create_argument_error(
+			format!( "`{}` expects a String, got {}", method_name, operations::repr( argument ) )
+		) ),
+	}
+}
+
+fn create_string( string: String ) -> rust::Result {
+	rust::Ok( value::String( Rc::new( string ) ) )
+}
+
+pub fn has_method( name: Identifier ) -> bool {
+	let mut name = name;
+	match name.get_value() {
+		"length" | "slice" | "find" | "split" | "join" | "replace" | "trim" | "upper" | "lower"
+		| "starts_with" | "ends_with" | "chars" => true,
+		_ => false,
+	}
+}
+
+pub fn call_method(
+	vm: &mut VirtualMachine,
+	string: &Rc<String>,
+	name: Identifier,
+	arguments: Vec<value::Value>,
+	keyword_arguments: Vec<(Identifier, value::Value)>
+) -> rust::Result {
+
+	let string = string.as_slice();
+	let mut name = name;
+	let method_name = name.get_value();
+	
+	macro_rules! try_string (
+		( $argument:expr ) => {{
+			match expect_string( method_name, $argument ) {
+				Ok( s ) => s,
+				Err( throwable ) => { return rust::Throw( throwable ); }
+			}
+		}}
+	)
+	
+	match method_name {
+		
+		"length" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			rust::Ok( value::Integer( string.char_len() as i64 ) )
+		}
+		
+		"slice" => {
+			let arguments = try_arguments!( &["start", "end"], arguments, keyword_arguments );
+			let length = string.char_len();
+			
+			let mut bounds = Vec::new();
+			for argument in arguments.iter() {
+				match list::clamp_bound( argument, "String", length ) {
+					Ok( bound ) => { bounds.push( bound ); }
+					Err( throwable ) => { return rust::Throw( throwable ); }
+				}
+			}
+			
+			let (start, end) = (*bounds.get( 0 ), *bounds.get( 1 ));
+			if start < end {
+				create_string( string.slice( byte_offset( string, start ), byte_offset( string, end ) ).to_string() )
+			} else {
+				create_string( String::new() )
+			}
+		}
+		
+		"find" => {
+			let arguments = try_arguments!( &["needle"], arguments, keyword_arguments );
+			let needle = try_string!( arguments.get( 0 ) );
+			match string.find_str( needle.as_slice() ) {
+				Some( offset ) => rust::Ok( value::Integer( string.slice_to( offset ).char_len() as i64 ) ),
+				None => rust::Ok( value::Nothing ),
+			}
+		}
+		
+		"split" => {
+			let arguments = try_arguments!( &["separator"], arguments, keyword_arguments );
+			let separator = try_string!( arguments.get( 0 ) );
+			if separator.len() == 0 {
+				return rust::Throw( create_argument_error( "Can't split by an empty String".to_string() ) );
+			}
+			let parts = string.split_str( separator.as_slice() ).map( |part| {
+				value::String( Rc::new( part.to_string() ) )
+			} ).collect();
+			rust::Ok( value::List( vm.lists.register( List::new( parts ) ) ) )
+		}
+		
+		"join" => {
+			let arguments = try_arguments!( &["items"], arguments, keyword_arguments );
+			match join( string, arguments.get( 0 ) ) {
+				Ok( joined ) => create_string( joined ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+		
+		"replace" => {
+			let arguments = try_arguments!( &["from", "to"], arguments, keyword_arguments );
+			let from = try_string!( arguments.get( 0 ) );
+			let to = try_string!( arguments.get( 1 ) );
+			if from.len() == 0 {
+				return rust::Throw( create_argument_error( "Can't replace an empty String".to_string() ) );
+			}
+			create_string( string.replace( from.as_slice(), to.as_slice() ) )
+		}
+		
+		"trim" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			create_string( string.trim().to_string() )
+		}
+		
+		"upper" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			create_string( string.chars().map( |c| { c.to_uppercase() } ).collect() )
+		}
+		
+		"lower" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			create_string( string.chars().map( |c| { c.to_lowercase() } ).collect() )
+		}
+		
+		"starts_with" => {
+			let arguments = try_arguments!( &["prefix"], arguments, keyword_arguments );
+			let prefix = try_string!( arguments.get( 0 ) );
+			rust::Ok( value::Boolean( string.starts_with( prefix.as_slice() ) ) )
+		}
+		
+		"ends_with" => {
+			let arguments = try_arguments!( &["suffix"], arguments, keyword_arguments );
+			let suffix = try_string!( arguments.get( 0 ) );
+			rust::Ok( value::Boolean( string.ends_with( suffix.as_slice() ) ) )
+		}
+		
+		"chars" => {
+			try_arguments!( &[], arguments, keyword_arguments );
+			let characters = string.chars().map( |c| {
+				let mut character = String::new();
+				character.push_char( c );
+				value::String( Rc::new( character ) )
+			} ).collect();
+			rust::Ok( value::List( vm.lists.register( List::new( characters ) ) ) )
+		}
+		
+		_ => { unreachable!(); }
+	}
+}
@@ -1,5 +1,14 @@
 use lang::value;
+use lang::value::Value;
+use lang::function::Function;
+use lang::operations;
+use builtin::burn::errors::create_type_error;
 use mem::rc::RefCounted;
+use mem::gc::Gc;
+use vm::virtual_machine::VirtualMachine;
+use vm::run::frame::Frame;
+use vm::run::rust;
+use vm::run::rust::Operation;
 
 pub struct TypeUnion {
 	pub left: value::Value,
@@ -23,4 +32,154 @@ pub struct TypeIntersection {
 	pub right: value::Value,
 }
 
+	impl TypeIntersection {
+		
+		pub fn new( left: value::Value, right: value::Value ) -> TypeIntersection {
+			TypeIntersection {
+				left: left,
+				right: right,
+			}
+		}
+	}
+	
 	impl RefCounted for TypeIntersection {}
+
+pub struct TypeComplement {
+	pub type_: value::Value,
+}
+
+	impl TypeComplement {
+		
+		pub fn new( type_: value::Value ) -> TypeComplement {
+			TypeComplement {
+				type_: type_,
+			}
+		}
+	}
+	
+	impl RefCounted for TypeComplement {}
+
+/// Tests a value against a type.
+///
+/// Composite types are tested one part at a time, short-circuiting like `and` and `or`.
+/// Predicate functions are Burn code, so the test suspends to call them and resumes with their result.
+pub struct TypeTest {
+	value: Value,
+	steps: Vec<TypeTestStep>,
+	predicate: Option<Gc<Function>>,
+}
+
+	enum TypeTestStep {
+		UnionStep( Value ),
+		IntersectionStep( Value ),
+		ComplementStep,
+	}
+	
+	pub enum TypeTestState {
+		Testing( Value ),
+		Tested( bool ),
+	}
+	
+	impl TypeTest {
+		
+		pub fn new( value: Value ) -> TypeTest {
+			TypeTest {
+				value: value,
+				steps: Vec::new(),
+				predicate: None,
+			}
+		}
+		
+		/// Test until there is a result, or until a predicate function has to be called.
+		/// In the latter case, the predicate is stored and `None` is returned.
+		pub fn advance( &mut self, mut state: TypeTestState ) -> Option<rust::Result> {
+			loop {
+				state = match state {
+					
+					Testing( type_ ) => {
+						match type_ {
+							
+							value::TypeUnion( ref r ) => {
+								self.steps.push( UnionStep( r.right.clone() ) );
+								Testing( r.left.clone() )
+							}
+							
+							value::TypeIntersection( ref r ) => {
+								self.steps.push( IntersectionStep( r.right.clone() ) );
+								Testing( r.left.clone() )
+							}
+							
+							value::TypeComplement( ref r ) => {
+								self.steps.push( ComplementStep );
+								Testing( r.type_.clone() )
+							}
+							
+							value::Function( ref f ) => {
+								self.predicate = Some( f.clone() );
+								return None;
+							}
+							
+							value::StaticSpecial( special ) => {
+								if ! special.is_type() {
+									return Some( throw_not_a_type( &type_ ) );
+								}
+								Tested( special.type_test( &self.value ) )
+							}
+							
+							value::RcSpecial( ref r ) => {
+								if ! r.is_type() {
+									return Some( throw_not_a_type( &type_ ) );
+								}
+								Tested( r.type_test( &self.value ) )
+							}
+							
+							_ => {
+								return Some( throw_not_a_type( &type_ ) );
+							}
+						}
+					}
+					
+					Tested( result ) => {
+						match self.steps.pop() {
+							None => { return Some( rust::Ok( value::Boolean( result ) ) ); }
+							Some( UnionStep( right ) ) => if result { Tested( true ) } else { Testing( right ) },
+							Some( IntersectionStep( right ) ) => if result { Testing( right ) } else { Tested( false ) },
+							Some( ComplementStep ) => Tested( ! result ),
+						}
+					}
+				};
+			}
+		}
+		
+		fn call_predicate( &mut self, vm: &mut VirtualMachine ) -> rust::Result {
+			let predicate = self.predicate.take().unwrap();
+			match Frame::new_function_call( vm, predicate, None, vec!( self.value.clone() ), Vec::new() ) {
+				Ok( frame ) => rust::Burn( frame ),
+				Err( throwable ) => rust::Throw( throwable ),
+			}
+		}
+	}
+	
+	impl Operation for TypeTest {
+		fn run( &mut self, vm: &mut VirtualMachine, result: Result<Value,Value> ) -> rust::Result {
+			
+			// the first run calls the predicate that suspended the test
+			if self.predicate.is_some() {
+				return self.call_predicate( vm );
+			}
+			
+			let tested = match result {
+				Ok( value ) => operations::is_truthy( &value ),
+				Err( throwable ) => { return rust::Throw( throwable ); }
+			};
+			
+			match self.advance( Tested( tested ) ) {
+				Some( result ) => result,
+				None => self.call_predicate( vm ),
+			}
+		}
+	}
+
+fn throw_not_a_type( value: &Value ) -> rust::Result {
+	rust::Throw( create_type_error( format!( "{} is not a type", operations::repr( value ) ) ) )
+}
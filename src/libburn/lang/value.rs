@@ -1,8 +1,13 @@
-use lang::function::Function;
+use lang::function::{Function, BoundMethod};
+use lang::class::Class;
+use lang::object::Object;
+use lang::list::List;
+use lang::map::Map;
+use num::bigint::BigInt;
 use mem::gc::Gc;
 use mem::rc::{Rc, RefCounted};
 use mem::raw::Raw;
-use lang::type_::{TypeUnion, TypeIntersection};
+use lang::type_::{TypeUnion, TypeIntersection, TypeComplement};
 use lang::module::Module;
 use lang::special::{StaticSpecial, RcSpecial};
 
@@ -16,6 +21,8 @@ pub enum Value {
 	#[doc(hidden)]
 	Integer( i64 ),
 	#[doc(hidden)]
+	BigInteger( Rc<BigInt> ),
+	#[doc(hidden)]
 	Float( f64 ),
 	#[doc(hidden)]
 	String( Rc<String> ),
@@ -27,7 +34,19 @@ pub enum Value {
 	#[doc(hidden)]
 	TypeIntersection( Rc<TypeIntersection> ),
 	#[doc(hidden)]
+	TypeComplement( Rc<TypeComplement> ),
+	#[doc(hidden)]
 	Module( Raw<Module> ),
+	#[doc(hidden)]
+	Class( Gc<Class> ),
+	#[doc(hidden)]
+	Object( Gc<Object> ),
+	#[doc(hidden)]
+	List( Gc<List> ),
+	#[doc(hidden)]
+	Map( Gc<Map> ),
+	#[doc(hidden)]
+	BoundMethod( Rc<BoundMethod> ),
 	
 	#[doc(hidden)]
 	StaticSpecial( StaticSpecial ),
@@ -13,6 +13,7 @@ extern crate serialize;
 extern crate libc;
 extern crate rustuv;
 extern crate debug;
+extern crate num;
 #[cfg(test)]
 extern crate test;
 
@@ -23,6 +24,16 @@ macro_rules! debug (
 	( $b:stmt ) => { if unsafe { ::DEBUG } { $b } }
 )
 
+// match the arguments of a method implemented in rust, or throw
+macro_rules! try_arguments (
+	( $parameter_names:expr, $arguments:expr, $keyword_arguments:expr ) => {{
+		match ::lang::function::match_rust_arguments( $parameter_names, $arguments, $keyword_arguments ) {
+			Ok( arguments ) => arguments,
+			Err( throwable ) => { return ::vm::run::rust::Throw( throwable ); }
+		}
+	}}
+)
+
 macro_rules! match_enum (
 	( $e:expr to $p:pat => $b:block ) => {
 		match $e {
@@ -50,8 +61,15 @@ mod lang {
 	
 	pub mod module;
 	pub mod function;
+	pub mod class;
+	pub mod object;
+	pub mod list;
+	pub mod map;
+	pub mod string;
+	pub mod integer;
 	pub mod type_;
 	pub mod special;
+	pub mod iteration;
 	
 	pub mod value;
 	pub mod operations;
@@ -25,6 +25,14 @@ pub struct Gc<T> {
 		}
 	}
 	
+	impl<T> PartialEq for Gc<T> {
+		fn eq( &self, other: &Gc<T> ) -> bool {
+			self.ptr == other.ptr
+		}
+	}
+	
+	impl<T> Eq for Gc<T> {}
+	
 	#[unsafe_destructor]
 	impl<T:GarbageCollected> Drop for Gc<T> {
 		fn drop( &mut self ) {
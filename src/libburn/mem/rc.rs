@@ -1,5 +1,6 @@
 use std::mem;
 use std::ptr;
+use num::bigint::BigInt;
 
 #[unsafe_no_drop_flag]
 pub struct Rc<T> {
@@ -31,6 +32,14 @@ pub struct Rc<T> {
 		}
 	}
 	
+	impl<T> PartialEq for Rc<T> {
+		fn eq( &self, other: &Rc<T> ) -> bool {
+			self.ptr == other.ptr
+		}
+	}
+	
+	impl<T> Eq for Rc<T> {}
+	
 	impl<T:RefCounted> Clone for Rc<T> {
 		fn clone( &self ) -> Rc<T> {
 			unsafe { (*self.ptr).rc += 1; }
@@ -63,6 +72,7 @@ struct RcWrapper<T> {
 pub trait RefCounted {}
 
 impl RefCounted for String {}
+impl RefCounted for BigInt {}
 
 #[cfg(test)]
 mod test {
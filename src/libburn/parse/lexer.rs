@@ -15,6 +15,15 @@ impl<'src> Lexer<'src> {
 		}
 	}
 	
+	/// Lex the part of `source` from `start` up to `end`, e.g. an interpolation inside a string literal.
+	/// Offsets are still relative to all of `source`.
+	pub fn new_range( source: &'src str, start: uint, end: uint ) -> Lexer<'src> {
+		Lexer {
+			source: source.slice_to( end ),
+			offset: start,
+		}
+	}
+	
 	// todo! make offset part of token
 	pub fn read( &mut self ) -> (token::Token<'src>, uint) {
 		loop {
@@ -104,7 +113,10 @@ impl<'src> Lexer<'src> {
 			']' => (token::RightSquareBracket, 1),
 			'(' => (token::LeftParenthesis, 1),
 			')' => (token::RightParenthesis, 1),
-			'.' => (token::Dot, 1),
+			'.' => match (self.peek_char( self.offset + 1 ), self.peek_char( self.offset + 2 )) {
+				(Some( '.' ), Some( '.' )) => (token::Ellipsis, 3),
+				_ => (token::Dot, 1),
+			},
 			',' => (token::Comma, 1),
 			'=' => match self.peek_char( self.offset + 1 ) {
 				Some( '=' ) => (token::EqualsEquals, 2),
@@ -147,6 +159,9 @@ impl<'src> Lexer<'src> {
 			},
 			'|' => (token::VerticalBar, 1),
 			
+			// raw string literals
+			'r' if self.peek_char( self.offset + 1 ) == Some( '"' ) => self.match_string_literal( 1 ),
+			
 			// identifier
 			'a'..'z' | 'A'..'Z' | '_' | ':' => {
 				
@@ -170,9 +185,12 @@ impl<'src> Lexer<'src> {
 				
 				let value = match sub {
 					"and" => token::And,
+					"break" => token::Break,
 					"catch" => token::Catch,
 					"class" => token::Class,
+					"continue" => token::Continue,
 					"else" => token::Else,
+					"extends" => token::Extends,
 					"false" => token::False,
 					"finally" => token::Finally,
 					"for" => token::For,
@@ -187,6 +205,7 @@ impl<'src> Lexer<'src> {
 					"or" => token::Or,
 					"print" => token::Print,
 					"return" => token::Return,
+					"super" => token::Super,
 					"this" => token::This,
 					"throw" => token::Throw,
 					"true" => token::True,
@@ -224,28 +243,7 @@ impl<'src> Lexer<'src> {
 			}
 			
 			// string literals
-			'"' => {
-				let mut length = 1;
-				loop {
-					match self.peek_char( self.offset + length ) {
-						Some( '\\' ) => {
-							length += 2;
-						}
-						Some( '"' ) => {
-							length += 1;
-							break;
-						}
-						Some( _ ) => {
-							length += utf8_char_width( self.source[ self.offset + length ] );
-						}
-						None => {
-							return (token::Error( "Unterminated string literal." ), 0);
-						}
-					}
-				}
-				let sub = self.source.slice( self.offset, self.offset + length );
-				(token::String( sub ), length)
-			}
+			'"' => self.match_string_literal( 0 ),
 			
 			'0'..'9' => self.match_number_literal(),
 			
@@ -254,6 +252,40 @@ impl<'src> Lexer<'src> {
 		}
 	}
 	
+	fn match_string_literal( &self, prefix_length: uint ) -> (token::Token<'src>, uint) {
+		let is_triple = self.source.slice_from( self.offset + prefix_length ).starts_with( "\"\"\"" );
+		let mut length = prefix_length + if is_triple { 3 } else { 1 };
+		loop {
+			match self.peek_char( self.offset + length ) {
+				Some( '\\' ) => {
+					length += 1;
+					if self.offset + length < self.source.len() {
+						length += utf8_char_width( self.source[ self.offset + length ] );
+					}
+				}
+				Some( '"' ) if is_triple => {
+					if self.source.slice_from( self.offset + length ).starts_with( "\"\"\"" ) {
+						length += 3;
+						break;
+					}
+					length += 1;
+				}
+				Some( '"' ) => {
+					length += 1;
+					break;
+				}
+				Some( _ ) => {
+					length += utf8_char_width( self.source[ self.offset + length ] );
+				}
+				None => {
+					return (token::Error( "Unterminated string literal." ), 0);
+				}
+			}
+		}
+		let sub = self.source.slice( self.offset, self.offset + length );
+		(token::String( sub ), length)
+	}
+	
 	fn match_number_literal( &self ) -> (token::Token<'src>, uint) {
 		
 		let mut l = 0;
@@ -267,32 +299,62 @@ impl<'src> Lexer<'src> {
 			_ => {},
 		}
 		
+		let radix = match (self.peek_char( self.offset + l ), self.peek_char( self.offset + l + 1 )) {
+			(Some( '0' ), Some( 'x' )) => 16,
+			(Some( '0' ), Some( 'o' )) => 8,
+			(Some( '0' ), Some( 'b' )) => 2,
+			_ => 10,
+		};
+		
+		if radix != 10 {
+			
+			l += 2;
+			
+			if ! self.is_digit( self.offset + l, radix ) {
+				return (token::Error( "Invalid number literal." ), 0);
+			}
+			
+			l += self.count_digits( self.offset + l, radix );
+			
+			let sub = self.source.slice( self.offset, self.offset + l );
+			return (token::Integer( sub ), l);
+		}
+		
 		match self.peek_char( self.offset + l ) {
 			Some( '0' ) => {
 				l += 1;
 				match self.peek_char( self.offset + l ) {
-					Some( '0'..'9' ) => {
+					Some( '0'..'9' ) | Some( '_' ) => {
 						return (token::Error( "Invalid number literal." ), 0);
 					},
 					_ => {}
 				}
 			},
-			Some( '1'..'9' ) => l += 1,
+			Some( '1'..'9' ) => l += self.count_digits( self.offset + l, 10 ),
 			_ => assert!( false )
 		}
 		
-		loop {
-			match self.peek_char( self.offset + l ) {
-				Some( '0'..'9' ) => l += 1,
-				Some( '.' ) if ! float => {
-					match self.peek_char( self.offset + l + 1 ) {
-						Some( '0'..'9' ) => l += 2,
-						_ => break,
-					}
+		// fraction
+		if self.peek_char( self.offset + l ) == Some( '.' ) && self.is_digit( self.offset + l + 1, 10 ) {
+			l += 1;
+			l += self.count_digits( self.offset + l, 10 );
+			float = true;
+		}
+		
+		// exponent
+		match self.peek_char( self.offset + l ) {
+			Some( 'e' ) | Some( 'E' ) => {
+				let sign = match self.peek_char( self.offset + l + 1 ) {
+					Some( '+' ) | Some( '-' ) => 1,
+					_ => 0,
+				};
+				if self.is_digit( self.offset + l + 1 + sign, 10 ) {
+					l += 1 + sign;
+					l += self.count_digits( self.offset + l, 10 );
 					float = true;
 				}
-				_ => break
 			}
+			_ => {}
 		}
 		
 		let sub = self.source.slice( self.offset, self.offset + l );
@@ -303,6 +365,27 @@ impl<'src> Lexer<'src> {
 			(token::Integer( sub ), l)
 		}
 	}
+	
+	fn is_digit( &self, offset: uint, radix: uint ) -> bool {
+		match self.peek_char( offset ) {
+			Some( c ) => c.is_digit_radix( radix ),
+			None => false,
+		}
+	}
+	
+	/// Count the digits starting at `offset`, including any `_` separating two of them.
+	fn count_digits( &self, offset: uint, radix: uint ) -> uint {
+		let mut l = 0;
+		loop {
+			if self.is_digit( offset + l, radix ) {
+				l += 1;
+			} else if self.peek_char( offset + l ) == Some( '_' ) && self.is_digit( offset + l + 1, radix ) {
+				l += 2;
+			} else {
+				return l;
+			}
+		}
+	}
 }
 
 #[cfg(test)]
@@ -372,6 +455,8 @@ mod test {
 		assert!( lex( "<" ) == vec!( token::LeftAngleBracket ) );
 		assert!( lex( ">" ) == vec!( token::RightAngleBracket ) );
 		assert!( lex( "." ) == vec!( token::Dot ) );
+		assert!( lex( "..." ) == vec!( token::Ellipsis ) );
+		assert!( lex( ".." ) == vec!( token::Dot, token::Dot ) );
 		assert!( lex( "," ) == vec!( token::Comma ) );
 		assert!( lex( "=" ) == vec!( token::Equals ) );
 		assert!( lex( "+" ) == vec!( token::Plus ) );
@@ -397,9 +482,12 @@ mod test {
 	#[test]
 	fn test_keywords() {
 		assert!( lex( "and" ) == vec!( token::And ) );
+		assert!( lex( "break" ) == vec!( token::Break ) );
 		assert!( lex( "catch" ) == vec!( token::Catch ) );
 		assert!( lex( "class" ) == vec!( token::Class ) );
+		assert!( lex( "continue" ) == vec!( token::Continue ) );
 		assert!( lex( "else" ) == vec!( token::Else ) );
+		assert!( lex( "extends" ) == vec!( token::Extends ) );
 		assert!( lex( "false" ) == vec!( token::False ) );
 		assert!( lex( "finally" ) == vec!( token::Finally ) );
 		assert!( lex( "for" ) == vec!( token::For ) );
@@ -413,6 +501,7 @@ mod test {
 		assert!( lex( "nothing" ) == vec!( token::Nothing ) );
 		assert!( lex( "or" ) == vec!( token::Or ) );
 		assert!( lex( "return" ) == vec!( token::Return ) );
+		assert!( lex( "super" ) == vec!( token::Super ) );
 		assert!( lex( "this" ) == vec!( token::This ) );
 		assert!( lex( "throw" ) == vec!( token::Throw ) );
 		assert!( lex( "true" ) == vec!( token::True ) );
@@ -464,9 +553,26 @@ mod test {
 		assert!( lex( "1." ) == vec!( token::Integer( "1" ), token::Dot ) );
 		assert!( lex( ".1" ) == vec!( token::Dot, token::Integer( "1" ) ) );
 		
+		assert!( lex( "1_000_000" ) == vec!( token::Integer( "1_000_000" ) ) );
+		assert!( lex( "1_" ) == vec!( token::Integer( "1" ), token::Identifier( "_" ) ) );
+		assert!( lex( "0_1" ) == vec!( token::Error( "Invalid number literal." ) ) );
+		assert!( lex( "0xFF_ff" ) == vec!( token::Integer( "0xFF_ff" ) ) );
+		assert!( lex( "-0o17" ) == vec!( token::Integer( "-0o17" ) ) );
+		assert!( lex( "0b1012" ) == vec!( token::Integer( "0b101" ), token::Integer( "2" ) ) );
+		assert!( lex( "0x" ) == vec!( token::Error( "Invalid number literal." ) ) );
+		assert!( lex( "1e9" ) == vec!( token::Float( "1e9" ) ) );
+		assert!( lex( "1.5E-9" ) == vec!( token::Float( "1.5E-9" ) ) );
+		assert!( lex( "2e+1_0" ) == vec!( token::Float( "2e+1_0" ) ) );
+		assert!( lex( "1e" ) == vec!( token::Integer( "1" ), token::Identifier( "e" ) ) );
+		
 		assert!( lex( "\"\"" ) == vec!( token::String( "\"\"" ) ) );
 		assert!( lex( "\"test\"" ) == vec!( token::String( "\"test\"" ) ) );
 		assert!( lex( "\"" ) == vec!( token::Error( "Unterminated string literal." ) ) );
+		assert!( lex( "r\"{a}\"" ) == vec!( token::String( "r\"{a}\"" ) ) );
+		assert!( lex( "r" ) == vec!( token::Identifier( "r" ) ) );
+		assert!( lex( "\"\"\"a\n\"b\"\"\"" ) == vec!( token::String( "\"\"\"a\n\"b\"\"\"" ) ) );
+		assert!( lex( "\"\"\"a\"\"" ) == vec!( token::Error( "Unterminated string literal." ) ) );
+		assert!( lex( "\"\\僯\"" ) == vec!( token::String( "\"\\僯\"" ) ) );
 	}
 	
 	#[test]
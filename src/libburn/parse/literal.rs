@@ -1,36 +1,108 @@
+use std::cmp;
+use std::num::from_str_radix;
+use std::char;
 use std::str::utf8_char_width;
+use num::bigint::BigInt;
 
+/// Parse an integer literal, e.g. `-42`, `1_000`, `0xFF`, `0o17` or `0b1010`.
+///
+/// Fails if it doesn't fit in an i64, see `parse_big_int`.
 pub fn parse_int( source: &str ) -> Result<i64,String> {
-	match from_str::<i64>( source ) {
+	let (digits, radix) = clean_int( source );
+	match from_str_radix::<i64>( digits.as_slice(), radix ) {
 		Some( i ) => Ok( i ),
 		None => Err( "Integer literal is out of range.".to_string() ),
 	}
 }
 
+/// Parse an integer literal of any size.
+pub fn parse_big_int( source: &str ) -> BigInt {
+	let (digits, radix) = clean_int( source );
+	from_str_radix::<BigInt>( digits.as_slice(), radix ).unwrap()
+}
+
+/// The sign and digits of an integer literal without its prefix and separators, and its radix.
+fn clean_int( source: &str ) -> (String, uint) {
+	
+	let (negative, unsigned) = match source.slice_shift_char() {
+		(Some( '-' ), rest) => (true, rest),
+		_ => (false, source),
+	};
+	
+	let (radix, digits) = match unsigned.slice_to( cmp::min( 2, unsigned.len() ) ) {
+		"0x" => (16, unsigned.slice_from( 2 )),
+		"0o" => (8, unsigned.slice_from( 2 )),
+		"0b" => (2, unsigned.slice_from( 2 )),
+		_ => (10, unsigned),
+	};
+	
+	let mut cleaned = String::new();
+	if negative {
+		cleaned.push_char( '-' );
+	}
+	cleaned.extend( digits.chars().filter( |&c| { c != '_' } ) );
+	
+	(cleaned, radix)
+}
+
+/// Parse a float literal, e.g. `3.14`, `1_000.5` or `1e-9`.
 pub fn parse_float( source: &str ) -> Result<f64,String> {
-	match from_str::<f64>( source ) {
-		Some( f ) => Ok( f ),
-		None => Err( "Float literal is out of range.".to_string() ),
+	
+	let cleaned = source.chars().filter( |&c| { c != '_' } ).collect::<String>();
+	
+	match from_str::<f64>( cleaned.as_slice() ) {
+		Some( f ) if ! f.is_infinite() => Ok( f ),
+		_ => Err( "Float literal is out of range.".to_string() ),
 	}
 }
 
-pub fn parse_string( source: &str ) -> Result<String,(String,uint)> {
+/// A part of a string literal.
+#[deriving(PartialEq, Eq, Show)]
+pub enum StringPart {
+	Text( String ),
+	/// The byte range of an interpolated expression, e.g. `$name` in `"hello {$name}"`.
+	Interpolation( uint, uint ),
+}
+
+/// Split a string literal into text and interpolations. Raw strings are never interpolated.
+///
+/// Errors carry the byte offset of the offending character within `source`.
+pub fn parse_string( source: &str ) -> Result<Vec<StringPart>,(String,uint)> {
+	let mut parts = Vec::new();
 	let mut buf = String::new();
-	let raw: bool;
-	let delimiter: char;
-	let mut i: uint;
 	
-	if source[0] as char == 'r' {
-		raw = true;
-		delimiter = source[1] as char;
-		i = 2;
-	} else {
-		raw = false;
-		delimiter = source[0] as char;
-		i = 1;
+	let raw = source[0] as char == 'r';
+	let mut i = if raw { 1 } else { 0 };
+	let triple = source.slice_from( i ).starts_with( "\"\"\"" );
+	let delimiter_length = if triple { 3 } else { 1 };
+	i += delimiter_length;
+	let mut end = source.len() - delimiter_length;
+	
+	// the indentation stripped from every line of a multi-line string
+	let mut indentation = 0;
+	
+	if triple && i < end && source[i] as char == '\n' {
+		
+		i += 1;
+		
+		// a last line of only whitespace holds the closing delimiter
+		match source.slice( i, end ).rfind( '\n' ) {
+			Some( n ) if count_indentation( source.slice( i + n + 1, end ) ) == end - ( i + n + 1 ) => {
+				end = i + n;
+			}
+			_ => {}
+		}
+		
+		indentation = source.slice( i, end ).lines()
+			.filter( |line| { count_indentation( *line ) < line.len() } )
+			.map( |line| { count_indentation( line ) } )
+			.min()
+			.unwrap_or( 0 );
+		
+		i += cmp::min( indentation, count_indentation( source.slice( i, end ) ) );
 	}
 	
-	loop {
+	while i < end {
 		match source[i] as char {
 			'\\' => {
 				match source[i+1] as char {
@@ -38,8 +110,8 @@ pub fn parse_string( source: &str ) -> Result<String,(String,uint)> {
 						buf.push_char( '\\' );
 						i += 2;
 					}
-					c @ _ if c == delimiter => {
-						buf.push_char( delimiter );
+					'"' => {
+						buf.push_char( '"' );
 						i += 2;
 					}
 					_ if raw => {
@@ -54,12 +126,56 @@ pub fn parse_string( source: &str ) -> Result<String,(String,uint)> {
 						buf.push_char( '\t' );
 						i += 2;
 					}
+					'r' => {
+						buf.push_char( '\r' );
+						i += 2;
+					}
+					'0' => {
+						buf.push_char( '\0' );
+						i += 2;
+					}
+					'{' => {
+						buf.push_char( '{' );
+						i += 2;
+					}
+					'x' => {
+						let (c, length) = try!( parse_hex_escape( source, i, end ) );
+						buf.push_char( c );
+						i += length;
+					}
+					'u' => {
+						let (c, length) = try!( parse_unicode_escape( source, i, end ) );
+						buf.push_char( c );
+						i += length;
+					}
 					_ => {
 						return Err( ("Invalid escape sequence".to_string(), i) );
 					}
 				}
 			},
-			c @ _ if c == delimiter => break,
+			'{' if ! raw => {
+				let start = i + 1;
+				let close = match source.slice( start, end ).find( '}' ) {
+					Some( length ) => start + length,
+					None => {
+						return Err( ("Unterminated interpolation".to_string(), i) );
+					}
+				};
+				if source.slice( start, close ).trim().len() == 0 {
+					return Err( ("Empty interpolation".to_string(), i) );
+				}
+				if buf.len() > 0 {
+					parts.push( Text( buf ) );
+					buf = String::new();
+				}
+				parts.push( Interpolation( start, close ) );
+				i = close + 1;
+			}
+			'\n' => {
+				buf.push_char( '\n' );
+				i += 1;
+				i += cmp::min( indentation, count_indentation( source.slice( i, end ) ) );
+			}
 			_ => {
 				buf.push_char( source.char_at( i ) );
 				i += utf8_char_width( source[ i ] );
@@ -67,13 +183,94 @@ pub fn parse_string( source: &str ) -> Result<String,(String,uint)> {
 		}
 	}
 	
-	Ok( buf.into_string() )
+	if buf.len() > 0 || parts.len() == 0 {
+		parts.push( Text( buf ) );
+	}
+	
+	Ok( parts )
+}
+
+/// The number of spaces and tabs a line starts with.
+fn count_indentation( line: &str ) -> uint {
+	line.chars().take_while( |&c| { c == ' ' || c == '\t' } ).count()
+}
+
+/// Parse `\xNN` at `i`, which must be at most `\x7F`.
+fn parse_hex_escape( source: &str, i: uint, end: uint ) -> Result<(char,uint),(String,uint)> {
+	
+	let mut value = 0u32;
+	
+	for j in range( i + 2, i + 4 ) {
+		let digit = if j < end { ( source[j] as char ).to_digit( 16 ) } else { None };
+		match digit {
+			Some( digit ) => { value = value * 16 + digit as u32; }
+			None => {
+				return Err( ("Expected two hex digits in `\\x` escape".to_string(), j) );
+			}
+		}
+	}
+	
+	if value > 0x7F {
+		return Err( ("`\\x` escapes must be at most `\\x7F`, use `\\u{...}` instead".to_string(), i) );
+	}
+	
+	Ok( (value as u8 as char, 4) )
+}
+
+/// Parse `\u{XXXX}` at `i`, with 1 to 6 hex digits naming a Unicode scalar value.
+fn parse_unicode_escape( source: &str, i: uint, end: uint ) -> Result<(char,uint),(String,uint)> {
+	
+	if i + 2 >= end || source[i+2] as char != '{' {
+		return Err( ("Expected `{` in `\\u` escape".to_string(), i + 2) );
+	}
+	
+	let mut value = 0u32;
+	let mut j = i + 3;
+	
+	loop {
+		
+		if j >= end {
+			return Err( ("Unterminated `\\u` escape".to_string(), i) );
+		}
+		
+		match source[j] as char {
+			'}' => { break; }
+			c => match c.to_digit( 16 ) {
+				Some( _ ) if j - ( i + 3 ) == 6 => {
+					return Err( ("A `\\u` escape has at most 6 hex digits".to_string(), j) );
+				}
+				Some( digit ) => { value = value * 16 + digit as u32; }
+				None => {
+					return Err( ("Invalid hex digit in `\\u` escape".to_string(), j) );
+				}
+			}
+		}
+		
+		j += 1;
+	}
+	
+	if j == i + 3 {
+		return Err( ("Empty `\\u` escape".to_string(), j) );
+	}
+	
+	if value >= 0xD800 && value <= 0xDFFF {
+		return Err( (format!( "Invalid `\\u` escape: {:X} is a surrogate", value ), i) );
+	}
+	
+	match char::from_u32( value ) {
+		Some( c ) => Ok( (c, j + 1 - i) ),
+		None => Err( (format!( "Invalid `\\u` escape: {:X} is out of range", value ), i) ),
+	}
 }
 
 #[cfg(test)]
 mod test {
 	
-	use super::{parse_int, parse_float, parse_string};
+	use super::{parse_int, parse_big_int, parse_float, parse_string, StringPart, Text, Interpolation};
+	
+	fn text( s: &str ) -> Result<Vec<StringPart>,(String,uint)> {
+		Ok( vec!( Text( s.to_string() ) ) )
+	}
 	
 	#[test]
 	fn test_parse_int() {
@@ -83,15 +280,76 @@ mod test {
 		assert!( parse_int( "-10" ) == Ok( -10 ) );
 		assert!( parse_int( "99999999999999999999999999999999999" )
 			== Err( "Integer literal is out of range.".to_string() ) );
+		assert!( parse_int( "1_000_000" ) == Ok( 1000000 ) );
+		assert!( parse_int( "0xFF_ff" ) == Ok( 0xffff ) );
+		assert!( parse_int( "-0x10" ) == Ok( -16 ) );
+		assert!( parse_int( "0o17" ) == Ok( 15 ) );
+		assert!( parse_int( "0b1010" ) == Ok( 10 ) );
+		assert!( parse_int( "0x7FFF_FFFF_FFFF_FFFF" ) == Ok( ::std::i64::MAX ) );
+		assert!( parse_int( "-0x8000_0000_0000_0000" ) == Ok( ::std::i64::MIN ) );
+		assert!( parse_int( "0x8000_0000_0000_0000" )
+			== Err( "Integer literal is out of range.".to_string() ) );
+	}
+	
+	#[test]
+	fn test_parse_big_int() {
+		assert!( parse_big_int( "0x8000_0000_0000_0000" ).to_string() == "9223372036854775808".to_string() );
+		assert!( parse_big_int( "-99_999_999_999_999_999_999" ).to_string() == "-99999999999999999999".to_string() );
 	}
 	
 	#[test]
 	fn test_parse_float() {
 		assert!( parse_float( "3.1" ) == Ok( 3.1 ) );
+		assert!( parse_float( "1_000.5" ) == Ok( 1000.5 ) );
+		assert!( parse_float( "1e-9" ) == Ok( 1e-9 ) );
+		assert!( parse_float( "2.5E3" ) == Ok( 2500.0 ) );
+		assert!( parse_float( "1e999" ) == Err( "Float literal is out of range.".to_string() ) );
 	}
 	
 	#[test]
 	fn test_parse_string() {
-		assert!( parse_string( r#""test""# ) == Ok( "test".to_string() ) );
+		assert!( parse_string( r#""test""# ) == Ok( vec!( Text( "test".to_string() ) ) ) );
+		assert!( parse_string( r#""""# ) == Ok( vec!( Text( "".to_string() ) ) ) );
+		assert!( parse_string( r#""a {$b} c""# ) == Ok( vec!(
+			Text( "a ".to_string() ),
+			Interpolation( 4, 6 ),
+			Text( " c".to_string() ),
+		) ) );
+		assert!( parse_string( r#""\{$b}""# ) == Ok( vec!( Text( "{$b}".to_string() ) ) ) );
+		assert!( parse_string( r#"r"{$b}""# ) == Ok( vec!( Text( "{$b}".to_string() ) ) ) );
+		assert!( parse_string( r#""{$b""# ) == Err( ("Unterminated interpolation".to_string(), 1) ) );
+		assert!( parse_string( r#""{ }""# ) == Err( ("Empty interpolation".to_string(), 1) ) );
+	}
+	
+	#[test]
+	fn test_parse_string_escapes() {
+		assert!( parse_string( r#""a\r\0b""# ) == text( "a\r\0b" ) );
+		assert!( parse_string( r#""\x41\x7f""# ) == text( "A\x7f" ) );
+		assert!( parse_string( r#""\u{41}\u{e9}\u{10FFFF}""# ) == text( "Aé\U0010ffff" ) );
+		assert!( parse_string( r#"r"\x41\u{41}""# ) == text( r"\x41\u{41}" ) );
+		
+		assert!( parse_string( r#""ab\q""# ) == Err( ("Invalid escape sequence".to_string(), 3) ) );
+		assert!( parse_string( r#""\x4""# ) == Err( ("Expected two hex digits in `\\x` escape".to_string(), 4) ) );
+		assert!( parse_string( r#""\xg1""# ) == Err( ("Expected two hex digits in `\\x` escape".to_string(), 3) ) );
+		assert!( parse_string( r#""a\x80""# ) == Err(
+			("`\\x` escapes must be at most `\\x7F`, use `\\u{...}` instead".to_string(), 2)
+		) );
+		assert!( parse_string( r#""\u41""# ) == Err( ("Expected `{` in `\\u` escape".to_string(), 3) ) );
+		assert!( parse_string( r#""\u{}""# ) == Err( ("Empty `\\u` escape".to_string(), 4) ) );
+		assert!( parse_string( r#""\u{4g}""# ) == Err( ("Invalid hex digit in `\\u` escape".to_string(), 5) ) );
+		assert!( parse_string( r#""\u{1000000}""# ) == Err( ("A `\\u` escape has at most 6 hex digits".to_string(), 10) ) );
+		assert!( parse_string( r#""\u{41""# ) == Err( ("Unterminated `\\u` escape".to_string(), 1) ) );
+		assert!( parse_string( r#""x\u{D800}""# ) == Err( ("Invalid `\\u` escape: D800 is a surrogate".to_string(), 2) ) );
+		assert!( parse_string( r#""\u{110000}""# ) == Err( ("Invalid `\\u` escape: 110000 is out of range".to_string(), 1) ) );
+	}
+	
+	#[test]
+	fn test_parse_multiline_string() {
+		assert!( parse_string( "\"\"\"a \"quoted\" word\"\"\"" ) == text( "a \"quoted\" word" ) );
+		assert!( parse_string( "\"\"\"\n\t\tfoo\n\t\t\tbar\n\n\t\tbaz\n\t\"\"\"" ) == text( "foo\n\tbar\n\nbaz" ) );
+		assert!( parse_string( "\"\"\"\n  foo\n  \"\"\"" ) == text( "foo" ) );
+		assert!( parse_string( "\"\"\"\n  foo\n bar\"\"\"" ) == text( " foo\nbar" ) );
+		assert!( parse_string( "r\"\"\"\n\t{a}\\n\n\t\"\"\"" ) == text( "{a}\\n" ) );
+		assert!( parse_string( "\"\"\"\n\t\\q\n\"\"\"" ) == Err( ("Invalid escape sequence".to_string(), 5) ) );
 	}
 }
@@ -1,4 +1,5 @@
 use std::vec::Vec;
+use num::bigint::BigInt;
 use mem::raw::Raw;
 use lang::identifier::Identifier;
 use vm::analysis::annotation;
@@ -26,6 +27,12 @@ pub enum Statement {
 		pub rvalue: Box<Expression>,
 	},
 	
+	AugmentedAssignment {
+		pub lvalue: Box<Lvalue>,
+		pub operator: ArithmeticOperator,
+		pub rvalue: Box<Expression>,
+	},
+	
 	Let {
 		pub variable_offset: uint,
 		pub variable_name: Identifier,
@@ -45,6 +52,14 @@ pub enum Statement {
 		pub expression: Box<Expression>,
 	},
 	
+	Break {
+		pub source_offset: uint,
+	},
+	
+	Continue {
+		pub source_offset: uint,
+	},
+	
 	If {
 		pub test: Box<Expression>,
 		pub block: Vec<Box<Statement>>,
@@ -64,6 +79,23 @@ pub enum Statement {
 		pub block: Vec<Box<Statement>>,
 		pub else_clause: Option<Box<Else>>,
 	},
+	
+	For {
+		pub variable_name: Identifier,
+		pub variable: Raw<annotation::Variable>,
+		pub iterable: Box<Expression>,
+		pub block: Vec<Box<Statement>>,
+		pub else_clause: Option<Box<Else>>,
+	},
+}
+
+/// The operator of an augmented assignment.
+pub enum ArithmeticOperator {
+	AddOperator,
+	SubtractOperator,
+	MultiplyOperator,
+	DivideOperator,
+	ModuloOperator,
 }
 
 pub struct ElseIf {
@@ -92,8 +124,24 @@ pub enum Expression {
 	
 	Function {
 		pub parameters: Vec<FunctionParameter>,
+		pub return_type: Option<Box<Expression>>,
 		pub frame: annotation::Frame,
 		pub block: Vec<Box<Statement>>,
+		pub is_method: bool,
+	},
+	
+	Class {
+		pub bases: Vec<Box<Expression>>,
+		pub properties: Vec<ClassProperty>,
+		pub methods: Vec<ClassMethod>,
+	},
+	
+	New {
+		pub expression: Box<Expression>,
+		pub arguments: Vec<Box<Expression>>,
+		/// The positions of arguments prefixed with `...`.
+		pub spread_arguments: Vec<uint>,
+		pub keyword_arguments: Vec<KeywordArgument>,
 	},
 	
 	And {
@@ -158,6 +206,10 @@ pub enum Expression {
 		pub left: Box<Expression>,
 		pub right: Box<Expression>,
 	},
+	Modulo {
+		pub left: Box<Expression>,
+		pub right: Box<Expression>,
+	},
 	
 	DotAccess {
 		pub expression: Box<Expression>,
@@ -172,6 +224,9 @@ pub enum Expression {
 	Call {
 		pub expression: Box<Expression>,
 		pub arguments: Vec<Box<Expression>>,
+		/// The positions of arguments prefixed with `...`.
+		pub spread_arguments: Vec<uint>,
+		pub keyword_arguments: Vec<KeywordArgument>,
 	},
 	
 	Variable {
@@ -185,12 +240,29 @@ pub enum Expression {
 		pub annotation: annotation::Name,
 	},
 	
+	This {
+		pub annotation: Raw<annotation::Variable>,
+		pub source_offset: uint,
+	},
+	
+	Super {
+		pub name: Identifier,
+		pub annotation: Raw<annotation::Variable>,
+		pub source_offset: uint,
+	},
+	
 	String {
 		pub value: ::std::string::String,
 	},
+	InterpolatedString {
+		pub parts: Vec<Box<Expression>>,
+	},
 	Integer {
 		pub value: i64,
 	},
+	BigInteger {
+		pub value: BigInt,
+	},
 	Float {
 		pub value: f64,
 	},
@@ -198,15 +270,37 @@ pub enum Expression {
 		pub value: bool,
 	},
 	Nothing,
+	List {
+		pub items: Vec<Box<Expression>>,
+	},
+	Map {
+		pub entries: Vec<(Box<Expression>, Box<Expression>)>,
+	},
 }
 
 pub struct FunctionParameter {
 	pub type_: Option<Box<Expression>>,
 	pub default: Option<Box<Expression>>,
+	pub is_rest: bool,
 	pub variable_name: Identifier,
 	pub variable: Raw<annotation::Variable>,
 }
 
+pub struct KeywordArgument {
+	pub name: Identifier,
+	pub value: Box<Expression>,
+}
+
+pub struct ClassProperty {
+	pub name: Identifier,
+	pub default: Option<Box<Expression>>,
+}
+
+pub struct ClassMethod {
+	pub name: Identifier,
+	pub function: Box<Expression>,
+}
+
 // LVALUES /////////////////////////////////////////////////////////////////////////////////////////
 
 pub enum Lvalue {
@@ -221,4 +315,9 @@ pub enum Lvalue {
 		pub expression: Box<Expression>,
 		pub name: Identifier,
 	},
+	
+	ItemAccessLvalue {
+		pub expression: Box<Expression>,
+		pub key_expression: Box<Expression>,
+	},
 }
@@ -1,4 +1,5 @@
 use std::vec::Vec;
+use std::cmp;
 use parse::token;
 use parse::lexer::Lexer;
 use parse::node;
@@ -8,6 +9,7 @@ use mem::raw::Raw;
 use mem::rc::Rc;
 use lang::origin::Origin;
 use lang::identifier::Identifier;
+use lang::function;
 use vm::error::ParseError;
 
 type ParseResult<T> = Result<T,ParseError>;
@@ -16,6 +18,7 @@ pub fn parse( origin: &Rc<Box<Origin>>, source_code: &str ) -> Result<node::Root
 	
 	let mut parsing = Parsing {
 		origin: origin,
+		source: source_code,
 		lexer: Lexer::new( source_code ),
 		buffer: Vec::new(),
 		newline_policy: HeedNewlines,
@@ -26,6 +29,7 @@ pub fn parse( origin: &Rc<Box<Origin>>, source_code: &str ) -> Result<node::Root
 
 struct Parsing<'o, 'src> {
 	origin: &'o Rc<Box<Origin>>,
+	source: &'src str,
 	lexer: Lexer<'src>,
 	buffer: Vec<(token::Token<'src>, uint)>,
 	newline_policy: NewlinePolicy,
@@ -112,8 +116,18 @@ struct Parsing<'o, 'src> {
 		}
 		
 		fn err( &mut self, message: String ) -> ParseError {
+			let offset = self.get_offset();
+			self.err_at( offset, message )
+		}
+		
+		fn err_at( &self, offset: uint, message: String ) -> ParseError {
+			self.err_span( offset, 1, message )
+		}
+		
+		fn err_span( &self, offset: uint, length: uint, message: String ) -> ParseError {
 			ParseError {
-				source_offset: self.get_offset(),
+				source_offset: offset,
+				source_length: length,
 				origin: self.origin.clone(),
 				message: message,
 			}
@@ -200,9 +214,12 @@ struct Parsing<'o, 'src> {
 				token::Let => self.parse_let_statement(),
 				token::Print => self.parse_print_statement(),
 				token::Throw => self.parse_throw_statement(),
+				token::Break => self.parse_break_statement(),
+				token::Continue => self.parse_continue_statement(),
 				
 				token::If => self.parse_if_statement(),
 				token::While => self.parse_while_statement(),
+				token::For => self.parse_for_statement(),
 				token::Try => self.parse_try_statement(),
 				
 				_ => {
@@ -220,6 +237,25 @@ struct Parsing<'o, 'src> {
 							rvalue: rvalue,
 						} )
 						
+					} else if is_augmented_assignment( self.peek() ) {
+						
+						let lvalue = try!( self.to_lvalue( expression ) );
+						let operator = match self.read() {
+							token::PlusEquals => node::AddOperator,
+							token::DashEquals => node::SubtractOperator,
+							token::AsteriskEquals => node::MultiplyOperator,
+							token::SlashEquals => node::DivideOperator,
+							token::PercentEquals => node::ModuloOperator,
+							_ => { unreachable!(); }
+						};
+						let rvalue = try!( self.parse_expression() );
+						
+						Ok( box node::AugmentedAssignment {
+							lvalue: lvalue,
+							operator: operator,
+							rvalue: rvalue,
+						} )
+						
 					} else {
 						
 						Ok( box node::ExpressionStatement {
@@ -355,6 +391,51 @@ struct Parsing<'o, 'src> {
 			} )
 		}
 		
+		fn parse_for_statement( &mut self ) -> ParseResult<Box<node::Statement>> {
+			
+			let keyword = self.read();
+			assert!( keyword == token::For );
+			
+			let previous_newline_policy = self.newline_policy;
+			self.newline_policy = IgnoreNewlines;
+			
+			let variable_name = match self.peek() {
+				token::Variable( name ) => {
+					self.read();
+					Identifier::find_or_create( name )
+				}
+				_ => return Err( self.err( "Expected variable.".to_string() ) )
+			};
+			
+			if self.peek() != token::In {
+				return Err( self.err( "Expected `in`.".to_string() ) );
+			}
+			self.read();
+			
+			let iterable = try!( self.parse_expression() );
+			let for_block = try!( self.parse_block() );
+			
+			let mut else_clause = None;
+			
+			if self.peek() == token::Else {
+				self.read();
+				let else_block = try!( self.parse_block() );
+				else_clause = Some( box node::Else {
+					block: else_block,
+				} );
+			}
+			
+			self.newline_policy = previous_newline_policy;
+			
+			Ok( box node::For {
+				variable_name: variable_name,
+				variable: Raw::null(),
+				iterable: iterable,
+				block: for_block,
+				else_clause: else_clause,
+			} )
+		}
+		
 		fn parse_try_statement( &mut self ) -> ParseResult<Box<node::Statement>> {
 			
 			let keyword = self.read();
@@ -484,6 +565,28 @@ struct Parsing<'o, 'src> {
 			} )
 		}
 		
+		fn parse_break_statement( &mut self ) -> ParseResult<Box<node::Statement>> {
+			
+			let source_offset = self.get_offset();
+			let keyword = self.read();
+			assert!( keyword == token::Break );
+			
+			Ok( box node::Break {
+				source_offset: source_offset,
+			} )
+		}
+		
+		fn parse_continue_statement( &mut self ) -> ParseResult<Box<node::Statement>> {
+			
+			let source_offset = self.get_offset();
+			let keyword = self.read();
+			assert!( keyword == token::Continue );
+			
+			Ok( box node::Continue {
+				source_offset: source_offset,
+			} )
+		}
+		
 		fn parse_expression( &mut self ) -> ParseResult<Box<node::Expression>> {
 			self.parse_op_expression( PRECEDENCE_ANY )
 		}
@@ -504,18 +607,20 @@ struct Parsing<'o, 'src> {
 			// Unary
 			//
 			
-			if min_precedence <= PRECEDENCE_NOT && self.peek() == token::Not {
+			// in a tighter context, e.g. `String + not Empty`, `not` binds as tightly as that context
+			let mut left = if self.peek() == token::Not {
 				self.read();
-				let expression = try!( self.parse_op_expression( PRECEDENCE_NOT + 1 ) );
-				return Ok( box node::Not { expression: expression } );
-			}
+				let operand_precedence = cmp::max( min_precedence, PRECEDENCE_NOT + 1 );
+				let expression = try!( self.parse_op_expression( operand_precedence ) );
+				box node::Not { expression: expression } as Box<node::Expression>
+			} else {
+				try!( self.parse_access_expression() )
+			};
 			
 			//
 			// Binary
 			//
 			
-			let mut left = try!( self.parse_access_expression() );
-			
 			if min_precedence > PRECEDENCE_MULTIPLICATIVE {
 				return Ok( left );
 			}
@@ -531,6 +636,11 @@ struct Parsing<'o, 'src> {
 					self.skip_newlines();
 					let right = try!( self.parse_op_expression( PRECEDENCE_MULTIPLICATIVE + 1 ) );
 					left = box node::Division { left: left, right: right };
+				} else if self.peek() == token::Percent {
+					self.read();
+					self.skip_newlines();
+					let right = try!( self.parse_op_expression( PRECEDENCE_MULTIPLICATIVE + 1 ) );
+					left = box node::Modulo { left: left, right: right };
 				} else {
 					break;
 				}
@@ -674,14 +784,37 @@ struct Parsing<'o, 'src> {
 						};
 					}
 					
+					token::LeftSquareBracket => {
+						self.read();
+						
+						let old_newline_policy = self.newline_policy;
+						self.newline_policy = IgnoreNewlines;
+						
+						let key_expression = try!( self.parse_expression() );
+						
+						if self.peek() != token::RightSquareBracket {
+							return Err( self.err( format!( "Expected {}.", token::RightSquareBracket ) ) );
+						}
+						self.read();
+						
+						self.newline_policy = old_newline_policy;
+						
+						expression = box node::ItemAccess {
+							expression: expression,
+							key_expression: key_expression,
+						};
+					}
+					
 					token::LeftParenthesis => {
 						self.read();
-						let arguments = try!( self.parse_arguments() );
+						let (arguments, spread_arguments, keyword_arguments) = try!( self.parse_arguments() );
 						let close = self.read();
 						assert!( close == token::RightParenthesis );
 						expression = box node::Call {
 							expression: expression,
 							arguments: arguments,
+							spread_arguments: spread_arguments,
+							keyword_arguments: keyword_arguments,
 						};
 					}
 					
@@ -692,17 +825,40 @@ struct Parsing<'o, 'src> {
 			Ok( expression )
 		}
 		
-		fn parse_arguments( &mut self ) -> ParseResult<Vec<Box<node::Expression>>> {
+		fn parse_arguments( &mut self ) -> ParseResult<(Vec<Box<node::Expression>>, Vec<uint>, Vec<node::KeywordArgument>)> {
+			
+			let mut arguments = Vec::<Box<node::Expression>>::new();
+			let mut spread_arguments = Vec::<uint>::new();
+			let mut keyword_arguments = Vec::<node::KeywordArgument>::new();
 			
 			if self.peek() == token::RightParenthesis {
-				return Ok( Vec::new() );
+				return Ok( (arguments, spread_arguments, keyword_arguments) );
 			}
 			
-			let mut arguments = Vec::<Box<node::Expression>>::new();
-			
 			loop {
 				
-				arguments.push( try!( self.parse_expression() ) );
+				match (self.peek(), self.peek_n(1)) {
+					
+					(token::Identifier( name ), token::Equals) => {
+						self.read();
+						self.read();
+						keyword_arguments.push( node::KeywordArgument {
+							name: Identifier::find_or_create( name ),
+							value: try!( self.parse_expression() ),
+						} );
+					}
+					
+					_ => {
+						if keyword_arguments.len() > 0 {
+							return Err( self.err( "Positional arguments can't follow keyword arguments.".to_string() ) );
+						}
+						if self.peek() == token::Ellipsis {
+							self.read();
+							spread_arguments.push( arguments.len() );
+						}
+						arguments.push( try!( self.parse_expression() ) );
+					}
+				}
 				
 				match self.peek() {
 					
@@ -712,7 +868,7 @@ struct Parsing<'o, 'src> {
 					}
 					
 					token::RightParenthesis => {
-						return Ok( arguments );
+						return Ok( (arguments, spread_arguments, keyword_arguments) );
 					}
 					
 					_ => {
@@ -727,6 +883,8 @@ struct Parsing<'o, 'src> {
 			match self.peek() {
 				
 				token::Function => self.parse_function(),
+				token::Class => self.parse_class(),
+				token::New => self.parse_new(),
 				
 				token::LeftParenthesis => {
 					
@@ -748,6 +906,70 @@ struct Parsing<'o, 'src> {
 					Ok( expr )
 				}
 				
+				token::LeftSquareBracket => {
+					
+					self.read();
+					
+					let old_newline_policy = self.newline_policy;
+					self.newline_policy = IgnoreNewlines;
+					
+					let mut items = Vec::new();
+					
+					while self.peek() != token::RightSquareBracket {
+						
+						items.push( try!( self.parse_expression() ) );
+						
+						match self.peek() {
+							token::Comma => { self.read(); }
+							token::RightSquareBracket => {}
+							_ => {
+								return Err( self.err( "Expected `,` or `]`.".to_string() ) );
+							}
+						}
+					}
+					self.read(); // ]
+					
+					self.newline_policy = old_newline_policy;
+					
+					Ok( box node::List { items: items } )
+				}
+				
+				token::LeftCurlyBracket => {
+					
+					self.read();
+					
+					let old_newline_policy = self.newline_policy;
+					self.newline_policy = IgnoreNewlines;
+					
+					let mut entries = Vec::new();
+					
+					while self.peek() != token::RightCurlyBracket {
+						
+						let key = try!( self.parse_expression() );
+						
+						if self.peek() != token::Arrow {
+							return Err( self.err( format!( "Expected {}.", token::Arrow ) ) );
+						}
+						self.read(); // ->
+						
+						let value = try!( self.parse_expression() );
+						entries.push( (key, value) );
+						
+						match self.peek() {
+							token::Comma => { self.read(); }
+							token::RightCurlyBracket => {}
+							_ => {
+								return Err( self.err( "Expected `,` or `}`.".to_string() ) );
+							}
+						}
+					}
+					self.read(); // }
+					
+					self.newline_policy = old_newline_policy;
+					
+					Ok( box node::Map { entries: entries } )
+				}
+				
 				token::Identifier( identifier ) => {
 					self.read();
 					Ok( box node::Name {
@@ -764,26 +986,61 @@ struct Parsing<'o, 'src> {
 						annotation: Raw::null(),
 					} )
 				}
+				token::This => {
+					let source_offset = self.get_offset();
+					self.read();
+					Ok( box node::This {
+						source_offset: source_offset,
+						annotation: Raw::null(),
+					} )
+				}
+				token::Super => {
+					let source_offset = self.get_offset();
+					self.read();
+					
+					if self.peek() != token::Dot {
+						return Err( self.err( "Expected `.`.".to_string() ) );
+					}
+					self.read();
+					
+					let name = match self.peek() {
+						token::Identifier( identifier ) => {
+							self.read();
+							identifier
+						}
+						_ => {
+							return Err( self.err( "Expected identifier.".to_string() ) );
+						}
+					};
+					
+					Ok( box node::Super {
+						name: Identifier::find_or_create( name ),
+						source_offset: source_offset,
+						annotation: Raw::null(),
+					} )
+				}
 				
 				token::String( source ) => {
+					let literal_offset = self.get_offset();
 					self.read();
 					match literal::parse_string( source ) {
-						Ok( value ) => Ok( box node::String { value: value } ),
-						Err( (message, _) ) => Err( self.err( message ) ),
+						Ok( parts ) => self.parse_string_parts( literal_offset, parts ),
+						Err( (message, offset) ) => Err( self.err_at( literal_offset + offset, message ) ),
 					}
 				}
 				token::Integer( source ) => {
 					self.read();
 					match literal::parse_int( source ) {
 						Ok( value ) => Ok( box node::Integer { value: value } ),
-						Err( e ) => Err( self.err( e ) ),
+						Err( _ ) => Ok( box node::BigInteger { value: literal::parse_big_int( source ) } ),
 					}
 				}
 				token::Float( source ) => {
+					let literal_offset = self.get_offset();
 					self.read();
 					match literal::parse_float( source ) {
 						Ok( value ) => Ok( box node::Float { value: value } ),
-						Err( e ) => Err( self.err( e ) ),
+						Err( e ) => Err( self.err_span( literal_offset, source.len(), e ) ),
 					}
 				}
 				token::True => {
@@ -805,6 +1062,52 @@ struct Parsing<'o, 'src> {
 			}
 		}
 		
+		fn parse_string_parts(
+			&mut self,
+			literal_offset: uint,
+			parts: Vec<literal::StringPart>
+		) -> ParseResult<Box<node::Expression>> {
+			
+			let mut expressions = Vec::<Box<node::Expression>>::new();
+			
+			for part in parts.move_iter() {
+				match part {
+					
+					literal::Text( text ) => {
+						expressions.push( box node::String { value: text } );
+					}
+					
+					literal::Interpolation( start, end ) => {
+						
+						let mut interpolation = Parsing {
+							origin: self.origin,
+							source: self.source,
+							lexer: Lexer::new_range( self.source, literal_offset + start, literal_offset + end ),
+							buffer: Vec::new(),
+							newline_policy: IgnoreNewlines,
+						};
+						
+						let expression = try!( interpolation.parse_expression() );
+						
+						if interpolation.peek() != token::Eof {
+							return Err( interpolation.err( "Expected `}`.".to_string() ) );
+						}
+						
+						expressions.push( expression );
+					}
+				}
+			}
+			
+			if expressions.len() == 1 {
+				match **expressions.get( 0 ) {
+					node::String {..} => { return Ok( expressions.pop().unwrap() ); }
+					_ => {}
+				}
+			}
+			
+			Ok( box node::InterpolatedString { parts: expressions } )
+		}
+		
 		fn parse_function( &mut self ) -> ParseResult<Box<node::Expression>> {
 			
 			let keyword = self.read();
@@ -813,6 +1116,17 @@ struct Parsing<'o, 'src> {
 			let previous_newline_policy = self.newline_policy;
 			self.newline_policy = IgnoreNewlines;
 			
+			let function = try!( self.parse_function_rest( false ) );
+			
+			self.newline_policy = previous_newline_policy;
+			
+			Ok( function )
+		}
+		
+		/// Parse the parameters and block of a function or method,
+		/// i.e. everything after the `function` keyword and (for methods) the name.
+		fn parse_function_rest( &mut self, is_method: bool ) -> ParseResult<Box<node::Expression>> {
+			
 			if self.peek() != token::LeftParenthesis {
 				return Err( self.err( "Expected `(`".to_string() ) );
 			}
@@ -825,14 +1139,183 @@ struct Parsing<'o, 'src> {
 			}
 			self.read();
 			
-			let block = try!( self.parse_block() );
+			let return_type = if self.peek() == token::Arrow {
+				self.read();
+				Some( try!( self.parse_expression() ) )
+			} else {
+				None
+			};
 			
-			self.newline_policy = previous_newline_policy;
+			let block = try!( self.parse_block() );
 			
 			Ok( box node::Function {
 				parameters: parameters,
+				return_type: return_type,
 				block: block,
 				frame: annotation::Frame::new_with_closure(),
+				is_method: is_method,
+			} )
+		}
+		
+		fn parse_class( &mut self ) -> ParseResult<Box<node::Expression>> {
+			
+			let keyword = self.read();
+			assert!( keyword == token::Class );
+			
+			let previous_newline_policy = self.newline_policy;
+			self.newline_policy = IgnoreNewlines;
+			
+			let mut bases = Vec::<Box<node::Expression>>::new();
+			
+			if self.peek() == token::Extends {
+				self.read();
+				
+				loop {
+					bases.push( try!( self.parse_expression() ) );
+					
+					if self.peek() == token::Comma {
+						self.read();
+					} else {
+						break;
+					}
+				}
+			}
+			
+			if self.peek() != token::LeftCurlyBracket {
+				return Err( self.err( "Expected `{`.".to_string() ) );
+			}
+			self.read();
+			
+			self.newline_policy = HeedNewlines;
+			self.skip_newlines();
+			
+			let mut properties = Vec::<node::ClassProperty>::new();
+			let mut methods = Vec::<node::ClassMethod>::new();
+			let mut names = Vec::<Identifier>::new();
+			
+			loop {
+				
+				if self.peek() == token::RightCurlyBracket {
+					break;
+				}
+				
+				match self.peek() {
+					
+					token::Function => {
+						
+						self.read();
+						
+						let name = match self.peek() {
+							token::Identifier( identifier ) => Identifier::find_or_create( identifier ),
+							_ => {
+								return Err( self.err( "Expected identifier.".to_string() ) );
+							}
+						};
+						
+						if names.contains( &name ) {
+							return Err( self.err( format!( "Duplicate class member `{}`.", name ) ) );
+						}
+						names.push( name );
+						self.read();
+						
+						self.newline_policy = IgnoreNewlines;
+						let function = try!( self.parse_function_rest( true ) );
+						self.newline_policy = HeedNewlines;
+						
+						methods.push( node::ClassMethod {
+							name: name,
+							function: function,
+						} );
+					}
+					
+					token::Identifier( identifier ) => {
+						
+						let name = Identifier::find_or_create( identifier );
+						
+						if names.contains( &name ) {
+							return Err( self.err( format!( "Duplicate class member `{}`.", name ) ) );
+						}
+						names.push( name );
+						self.read();
+						
+						let default = if self.peek() == token::Equals {
+							self.read();
+							self.skip_newlines();
+							Some( try!( self.parse_expression() ) )
+						} else {
+							None
+						};
+						
+						properties.push( node::ClassProperty {
+							name: name,
+							default: default,
+						} );
+					}
+					
+					t @ _ => {
+						return Err( self.err( format!( "Unexpected {}.", t ) ) );
+					}
+				}
+				
+				match self.peek() {
+					token::Newline => self.skip_newlines(),
+					token::RightCurlyBracket => break,
+					_ => return Err( self.err( "Expected newline.".to_string() ) )
+				}
+			}
+			
+			let closing = self.read();
+			assert!( closing == token::RightCurlyBracket );
+			
+			self.newline_policy = previous_newline_policy;
+			
+			Ok( box node::Class {
+				bases: bases,
+				properties: properties,
+				methods: methods,
+			} )
+		}
+		
+		fn parse_new( &mut self ) -> ParseResult<Box<node::Expression>> {
+			
+			let keyword = self.read();
+			assert!( keyword == token::New );
+			
+			let mut expression = try!( self.parse_atom_expression() );
+			
+			while self.peek() == token::Dot {
+				self.read();
+				
+				let name = match self.peek() {
+					token::Identifier( identifier ) => {
+						self.read();
+						identifier
+					}
+					_ => {
+						return Err( self.err( "Expected identifier.".to_string() ) );
+					}
+				};
+				
+				expression = box node::DotAccess {
+					expression: expression,
+					name: Identifier::find_or_create( name ),
+				};
+			}
+			
+			if self.peek() != token::LeftParenthesis {
+				return Err( self.err( "Expected `(`.".to_string() ) );
+			}
+			self.read();
+			
+			let (arguments, spread_arguments, keyword_arguments) = try!( self.parse_arguments() );
+			let close = self.read();
+			assert!( close == token::RightParenthesis );
+			
+			Ok( box node::New {
+				expression: expression,
+				arguments: arguments,
+				spread_arguments: spread_arguments,
+				keyword_arguments: keyword_arguments,
 			} )
 		}
 		
@@ -845,6 +1328,36 @@ struct Parsing<'o, 'src> {
 			}
 			
 			loop {
+				
+				if self.peek() == token::Ellipsis {
+					self.read();
+					
+					let variable_name = match self.peek() {
+						token::Variable( name ) => {
+							self.read();
+							Identifier::find_or_create( name )
+						}
+						_ => return Err( self.err( "Expected variable".to_string() ) )
+					};
+					
+					parameters.push( node::FunctionParameter {
+						type_: None,
+						default: None,
+						is_rest: true,
+						variable_name: variable_name,
+						variable: Raw::null(),
+					} );
+					
+					if self.peek() != token::RightParenthesis {
+						return Err( self.err( "Expected `)`: the rest argument must be the last one.".to_string() ) );
+					}
+					break;
+				}
+				
+				if parameters.len() == function::MAX_PARAMETERS {
+					return Err( self.err( format!( "Functions can have at most {} parameters.", function::MAX_PARAMETERS ) ) );
+				}
+				
 				let type_ = match self.peek() {
 					token::Variable(..) => match self.peek_n(1) {
 						token::Equals | token::Comma | token::RightParenthesis => None,
@@ -871,6 +1384,7 @@ struct Parsing<'o, 'src> {
 				parameters.push( node::FunctionParameter {
 					type_: type_,
 					default: default,
+					is_rest: false,
 					variable_name: variable_name,
 					variable: Raw::null(),
 				} );
@@ -912,7 +1426,29 @@ struct Parsing<'o, 'src> {
 					} )
 				}
 				
+				node::ItemAccess {
+					expression: expression,
+					key_expression: key_expression,
+				} => {
+					Ok( box node::ItemAccessLvalue {
+						expression: expression,
+						key_expression: key_expression,
+					} )
+				}
+				
 				_ => Err( self.err( "Invalid lvalue".to_string() ) )
 			}
 		}
 	}
+
+fn is_augmented_assignment( token: token::Token ) -> bool {
+	match token {
+		token::PlusEquals
+		| token::DashEquals
+		| token::AsteriskEquals
+		| token::SlashEquals
+		| token::PercentEquals
+		=> true,
+		_ => false,
+	}
+}
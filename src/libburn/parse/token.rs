@@ -14,6 +14,7 @@ pub enum Token<'src> {
 	LeftAngleBracket, // <
 	RightAngleBracket, // >
 	Dot, // .
+	Ellipsis, // ...
 	Comma, // ,
 	VerticalBar, // |
 	Equals, // =
@@ -36,9 +37,12 @@ pub enum Token<'src> {
 	Arrow, // ->
 	
 	And,
+	Break,
 	Catch,
 	Class,
+	Continue,
 	Else,
+	Extends,
 	False,
 	Finally,
 	For,
@@ -53,6 +57,7 @@ pub enum Token<'src> {
 	Or,
 	Print,
 	Return,
+	Super,
 	This,
 	Throw,
 	True,
@@ -88,6 +93,7 @@ pub enum Token<'src> {
 				LeftAngleBracket => write!( f, "`<`" ),
 				RightAngleBracket => write!( f, "`>`" ),
 				Dot => write!( f, "`.`" ),
+				Ellipsis => write!( f, "`...`" ),
 				Comma => write!( f, "`,`" ),
 				VerticalBar => write!( f, "`|`" ),
 				Equals => write!( f, "`=`" ),
@@ -110,9 +116,12 @@ pub enum Token<'src> {
 				Arrow => write!( f, "`->`" ),
 				
 				And => write!( f, "and" ),
+				Break => write!( f, "break" ),
 				Catch => write!( f, "catch" ),
 				Class => write!( f, "class" ),
+				Continue => write!( f, "continue" ),
 				Else => write!( f, "else" ),
+				Extends => write!( f, "extends" ),
 				False => write!( f, "false" ),
 				Finally => write!( f, "finally" ),
 				For => write!( f, "for" ),
@@ -127,6 +136,7 @@ pub enum Token<'src> {
 				Or => write!( f, "or" ),
 				Print => write!( f, "print" ),
 				Return => write!( f, "return" ),
+				Super => write!( f, "super" ),
 				This => write!( f, "this" ),
 				Throw => write!( f, "throw" ),
 				True => write!( f, "true" ),
@@ -10,6 +10,9 @@ pub struct Frame {
 	pub n_local_variables: uint,
 	pub n_shared_local_variables: uint,
 	pub closure: Option<Closure>,
+	pub this: Option<Raw<Variable>>,
+	/// Holds the declared return type of a function, evaluated when it is called.
+	pub return_type: Option<Raw<Variable>>,
 }
 
 	impl Frame {
@@ -21,6 +24,8 @@ pub struct Frame {
 				n_local_variables: 0,
 				n_shared_local_variables: 0,
 				closure: None,
+				this: None,
+				return_type: None,
 			}
 		}
 		
@@ -31,6 +36,8 @@ pub struct Frame {
 				n_local_variables: 0,
 				n_shared_local_variables: 0,
 				closure: Some( Closure::new() ),
+				this: None,
+				return_type: None,
 			}
 		}
 		
@@ -1,3 +1,4 @@
+use std::mem;
 use mem::raw::Raw;
 use mem::rc::Rc;
 use lang::origin::Origin;
@@ -18,6 +19,8 @@ pub struct AnalyzeResolution<'o> {
 	frames: Vec<Raw<annotation::Frame>>,
 	scopes: Vec<Scope>,
 	time: annotation::Time,
+	/// The number of loops around the current statement, within the current frame.
+	n_loops: uint,
 	pub errors: Vec<Box<Error>>,
 }
 
@@ -29,6 +32,7 @@ pub struct AnalyzeResolution<'o> {
 				frames: Vec::new(),
 				scopes: Vec::new(),
 				time: 0,
+				n_loops: 0,
 				errors: Vec::new(),
 			}
 		}
@@ -83,6 +87,18 @@ pub struct AnalyzeResolution<'o> {
 					self.analyze_expression( *expression );
 				}
 				
+				node::Break { source_offset: source_offset } => {
+					if self.n_loops == 0 {
+						self.error( "`break` can only be used inside loops.".to_string(), source_offset );
+					}
+				}
+				
+				node::Continue { source_offset: source_offset } => {
+					if self.n_loops == 0 {
+						self.error( "`continue` can only be used inside loops.".to_string(), source_offset );
+					}
+				}
+				
 				node::Return { expression: ref mut optional_expression }
 				=> {
 					match *optional_expression {
@@ -130,6 +146,17 @@ pub struct AnalyzeResolution<'o> {
 					self.analyze_lvalue_write( *lvalue );
 				}
 				
+				node::AugmentedAssignment {
+					lvalue: ref mut lvalue,
+					operator: _,
+					rvalue: ref mut rvalue,
+				} => {
+					self.analyze_lvalue_preparation( *lvalue );
+					self.analyze_lvalue_read( *lvalue );
+					self.analyze_expression( *rvalue );
+					self.analyze_lvalue_write( *lvalue );
+				}
+				
 				node::If {
 					test: ref mut if_test,
 					block: ref mut if_block,
@@ -168,7 +195,39 @@ pub struct AnalyzeResolution<'o> {
 					self.analyze_expression( *while_test );
 					self.push_scope();
 					let start = self.tick();
+					self.n_loops += 1;
 					self.analyze_block( while_block );
+					self.n_loops -= 1;
+					let end = self.tick();
+					self.repeat_variable_usages( start, end );
+					self.pop_scope();
+					
+					match *else_clause {
+						Some( ref mut else_clause ) => {
+							self.push_scope();
+							self.analyze_block( &mut else_clause.block );
+							self.pop_scope();
+						}
+						None => {}
+					}
+				}
+				
+				node::For {
+					variable_name: name,
+					variable: ref mut variable,
+					iterable: ref mut iterable,
+					block: ref mut for_block,
+					else_clause: ref mut else_clause,
+				} => {
+					
+					self.analyze_expression( *iterable );
+					self.push_scope();
+					let start = self.tick();
+					*variable = self.declare_variable( name );
+					self.write_variable( *variable );
+					self.n_loops += 1;
+					self.analyze_block( for_block );
+					self.n_loops -= 1;
 					let end = self.tick();
 					self.repeat_variable_usages( start, end );
 					self.pop_scope();
@@ -243,10 +302,36 @@ pub struct AnalyzeResolution<'o> {
 				node::Nothing
 				| node::Boolean {..}
 				| node::Integer {..}
+				| node::BigInteger {..}
 				| node::Float {..}
 				| node::String {..}
 				=> {}
 				
+				node::InterpolatedString {
+					parts: ref mut parts,
+				} => {
+					for part in parts.mut_iter() {
+						self.analyze_expression( *part );
+					}
+				}
+				
+				node::List {
+					items: ref mut items,
+				} => {
+					for item in items.mut_iter() {
+						self.analyze_expression( *item );
+					}
+				}
+				
+				node::Map {
+					entries: ref mut entries,
+				} => {
+					for &(ref mut key, ref mut value) in entries.mut_iter() {
+						self.analyze_expression( *key );
+						self.analyze_expression( *value );
+					}
+				}
+				
 				node::Variable {
 					name: name,
 					annotation: ref mut annotation,
@@ -291,15 +376,21 @@ pub struct AnalyzeResolution<'o> {
 				node::Call {
 					expression: ref mut expression,
 					arguments: ref mut arguments,
+					spread_arguments: _,
+					keyword_arguments: ref mut keyword_arguments,
 				} => {
 					self.analyze_expression( *expression );
 					for argument in arguments.mut_iter() {
 						self.analyze_expression( *argument );
 					}
+					for keyword_argument in keyword_arguments.mut_iter() {
+						self.analyze_expression( keyword_argument.value );
+					}
 				}
 				
 				node::Multiplication { left: ref mut left, right: ref mut right }
 				| node::Division { left: ref mut left, right: ref mut right }
+				| node::Modulo { left: ref mut left, right: ref mut right }
 				| node::Addition { left: ref mut left, right: ref mut right }
 				| node::Subtraction { left: ref mut left, right: ref mut right }
 				| node::Union { left: ref mut left, right: ref mut right }
@@ -323,10 +414,88 @@ pub struct AnalyzeResolution<'o> {
 					self.analyze_expression( *expression );
 				}
 				
+				node::This {
+					annotation: ref mut annotation,
+					source_offset: source_offset,
+				} => {
+					match self.find_this() {
+						Some( variable ) => {
+							*annotation = variable;
+							self.read_variable( variable );
+						}
+						None => {
+							self.error(
+								"`this` can only be used inside methods.".to_string(),
+								source_offset
+							);
+						}
+					};
+				}
+				
+				node::Super {
+					annotation: ref mut annotation,
+					source_offset: source_offset,
+					..
+				} => {
+					match self.find_this() {
+						Some( variable ) => {
+							*annotation = variable;
+							self.read_variable( variable );
+						}
+						None => {
+							self.error(
+								"`super` can only be used inside methods.".to_string(),
+								source_offset
+							);
+						}
+					};
+				}
+				
+				node::New {
+					expression: ref mut expression,
+					arguments: ref mut arguments,
+					spread_arguments: _,
+					keyword_arguments: ref mut keyword_arguments,
+				} => {
+					self.analyze_expression( *expression );
+					for argument in arguments.mut_iter() {
+						self.analyze_expression( *argument );
+					}
+					for keyword_argument in keyword_arguments.mut_iter() {
+						self.analyze_expression( keyword_argument.value );
+					}
+				}
+				
+				node::Class {
+					bases: ref mut bases,
+					properties: ref mut properties,
+					methods: ref mut methods,
+				} => {
+					
+					for base in bases.mut_iter() {
+						self.analyze_expression( *base );
+					}
+					
+					for property in properties.mut_iter() {
+						match property.default {
+							Some( ref mut expression ) => {
+								self.analyze_expression( *expression );
+							}
+							None => {},
+						};
+					}
+					
+					for method in methods.mut_iter() {
+						self.analyze_expression( method.function );
+					}
+				}
+				
 				node::Function {
 					parameters: ref mut parameters,
+					return_type: ref mut return_type,
 					frame: ref mut frame,
 					block: ref mut block,
+					is_method: is_method,
 				} => {
 					
 					frame.get_closure().created_at = self.tick();
@@ -335,6 +504,10 @@ pub struct AnalyzeResolution<'o> {
 					
 					self.push_frame( frame );
 					
+					if is_method {
+						frame.this = Some( self.declare_this() );
+					}
+					
 					for parameter in parameters.mut_iter() {
 						match parameter.type_ {
 							Some( ref mut expression ) => {
@@ -350,11 +523,22 @@ pub struct AnalyzeResolution<'o> {
 						};
 					}
 					
+					match *return_type {
+						Some( ref mut expression ) => {
+							self.analyze_expression( *expression );
+							frame.return_type = Some( self.declare_implicit_variable( "return type" ) );
+						}
+						None => {},
+					};
+					
 					self.push_scope();
 					for parameter in parameters.mut_iter() {
 						parameter.variable = self.declare_variable( parameter.variable_name );
 					}
+					// loops around the function can't be broken out of from inside it
+					let n_loops = mem::replace( &mut self.n_loops, 0 );
 					self.analyze_block( block );
+					self.n_loops = n_loops;
 					self.pop_scope();
 					self.pop_frame();
 				}
@@ -388,6 +572,32 @@ pub struct AnalyzeResolution<'o> {
 				} => {
 					self.analyze_expression( *expression );
 				}
+				
+				node::ItemAccessLvalue {
+					expression: ref mut expression,
+					key_expression: ref mut key_expression,
+				} => {
+					self.analyze_expression( *expression );
+					self.analyze_expression( *key_expression );
+				}
+			}
+		}
+		
+		fn analyze_lvalue_read( &mut self, lvalue: &mut node::Lvalue ) {
+			match *lvalue {
+				
+				node::VariableLvalue {
+					name: _,
+					annotation: annotation,
+					source_offset: _,
+				} => {
+					// the variable might not have been found
+					if ! annotation.is_null() {
+						self.read_variable( annotation );
+					}
+				}
+				
+				node::DotAccessLvalue {..} | node::ItemAccessLvalue {..} => {}
 			}
 		}
 		
@@ -405,7 +615,7 @@ pub struct AnalyzeResolution<'o> {
 					}
 				}
 				
-				node::DotAccessLvalue {..} => {}
+				node::DotAccessLvalue {..} | node::ItemAccessLvalue {..} => {}
 			}
 		}
 		
@@ -467,6 +677,36 @@ pub struct AnalyzeResolution<'o> {
 			ptr
 		}
 		
+		/// Declare the implicit variable holding a method's receiver.
+		/// It is not added to any scope, so it can only be accessed through `this`.
+		fn declare_this( &mut self ) -> Raw<annotation::Variable> {
+			self.declare_implicit_variable( "this" )
+		}
+		
+		/// Declare a variable in the current frame that is not added to any scope,
+		/// so it can't be accessed by name.
+		fn declare_implicit_variable( &mut self, name: &str ) -> Raw<annotation::Variable> {
+			
+			let mut variable = box annotation::Variable::new( Identifier::find_or_create( name ) );
+			let ptr = Raw::new( variable );
+			
+			variable.declared_in = self.get_current_frame();
+			self.get_current_frame().declared_variables.push( variable );
+			
+			ptr
+		}
+		
+		fn find_this( &self ) -> Option<Raw<annotation::Variable>> {
+			
+			for frame in self.frames.iter().rev() {
+				if frame.this.is_some() {
+					return frame.this;
+				}
+			}
+			
+			None
+		}
+		
 		fn find_variable( &mut self, name: Identifier ) -> Result<Raw<annotation::Variable>,()> {
 			
 			for scope in self.scopes.iter().rev() {
@@ -1,5 +1,8 @@
 use mem::rc::Rc;
+use num::bigint::BigInt;
 use lang::function::FunctionDefinition;
+use lang::class::ClassDefinition;
+use lang::identifier::Identifier;
 use vm::bytecode::opcode;
 
 pub struct Code {
@@ -7,7 +10,14 @@ pub struct Code {
 	pub n_shared_local_variables: uint,
 	pub opcodes: Vec<opcode::OpCode>,
 	pub strings: Vec<Rc<String>>,
+	/// Integer literals that don't fit in an i64.
+	pub big_integers: Vec<Rc<BigInt>>,
 	pub functions: Vec<Rc<FunctionDefinition>>,
+	pub classes: Vec<Rc<ClassDefinition>>,
+	/// The names of keyword arguments, for each call that has them.
+	pub keywords: Vec<Vec<Identifier>>,
+	/// The positions of spread arguments, for each call that has them.
+	pub spreads: Vec<Vec<uint>>,
 }
 
 	impl Code {
@@ -18,7 +28,11 @@ pub struct Code {
 				n_shared_local_variables: 0,
 				opcodes: Vec::new(),
 				strings: Vec::new(),
+				big_integers: Vec::new(),
 				functions: Vec::new(),
+				classes: Vec::new(),
+				keywords: Vec::new(),
+				spreads: Vec::new(),
 			}
 		}
 		
@@ -35,6 +49,7 @@ pub struct Code {
 				println!( "{}    {}: {:?}", indent, i, c );
 			}
 			println!( "{}  strings: {}", indent, self.strings.len() );
+			println!( "{}  big_integers: {}", indent, self.big_integers.len() );
 			println!( "{}  functions: {}", indent, self.functions.len() );
 			for (i, f) in self.functions.iter().enumerate() {
 				println!( "{}    {}: \\{", indent, i );
@@ -43,6 +58,7 @@ pub struct Code {
 				let n = indent.len();
 				indent.truncate( n - 4 );
 			}
+			println!( "{}  classes: {}", indent, self.classes.len() );
 			println!( "{}\\}", indent );
 		}
 	}
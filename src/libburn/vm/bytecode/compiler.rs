@@ -4,6 +4,7 @@ use mem::rc::Rc;
 use parse::{parser, node};
 use lang::origin::Origin;
 use lang::function;
+use lang::class;
 use lang::value;
 use vm::error::Error;
 use vm::bytecode::code::Code;
@@ -66,6 +67,9 @@ pub fn compile(
 struct Compilation {
 	code: Box<Code>,
 	frames: Vec<Raw<annotation::Frame>>,
+	loops: Vec<Loop>,
+	/// The number of flow points the compiled code has pushed at the current instruction.
+	n_flow_points: uint,
 }
 
 	type Placeholder = uint;
@@ -76,6 +80,8 @@ struct Compilation {
 			Compilation {
 				code: box Code::new(),
 				frames: Vec::new(),
+				loops: Vec::new(),
+				n_flow_points: 0,
 			}
 		}
 		
@@ -114,21 +120,228 @@ struct Compilation {
 			self.frames.pop();
 		}
 		
-		fn compile_function( &mut self, frame: &annotation::Frame, block: &mut [Box<node::Statement>] ) {
+		fn compile_function(
+			&mut self,
+			frame: &annotation::Frame,
+			parameters: &mut [node::FunctionParameter],
+			return_type: &mut Option<Box<node::Expression>>,
+			block: &mut [Box<node::Statement>]
+		) {
 			
 			self.frames.push( Raw::new( frame ) );
 			
 			self.code.n_local_variables = frame.n_local_variables;
 			self.code.n_shared_local_variables = frame.n_shared_local_variables;
 			
+			// defaults and types are evaluated on every call, before the body runs
+			for (i, parameter) in parameters.mut_iter().enumerate() {
+				
+				match parameter.default {
+					Some( ref mut default ) => {
+						let placeholder = self.create_placeholder();
+						self.compile_expression( *default );
+						self.compile_variable_initialization( parameter.variable );
+						let jump = opcode::JumpIfArgumentGiven { parameter: i, instruction: self.code.opcodes.len() };
+						self.fill_in_placeholder( placeholder, jump );
+					}
+					None => {}
+				}
+				
+				match parameter.type_ {
+					Some( ref mut type_ ) => {
+						let variable = parameter.variable;
+						self.compile_variable_load( variable );
+						self.compile_expression( *type_ );
+						self.code.opcodes.push( opcode::Is );
+						self.code.opcodes.push( match variable.local_storage_type {
+							annotation::storage::Local => opcode::TypeCheckLocal {
+								index: variable.local_storage_index,
+								name: variable.name,
+							},
+							annotation::storage::SharedLocal => opcode::TypeCheckSharedLocal {
+								index: variable.local_storage_index,
+								name: variable.name,
+							},
+						} );
+					}
+					None => {}
+				}
+			}
+			
+			match *return_type {
+				Some( ref mut type_ ) => {
+					self.compile_expression( *type_ );
+					self.compile_variable_initialization( frame.return_type.unwrap() );
+				}
+				None => {}
+			}
+			
 			for statement in block.mut_iter() {
 				self.compile_statement( *statement );
 			}
-			self.code.opcodes.push( opcode::ReturnNothing );
+			
+			if frame.return_type.is_some() {
+				self.code.opcodes.push( opcode::PushNothing );
+				self.compile_return();
+			} else {
+				self.code.opcodes.push( opcode::ReturnNothing );
+			}
 			
 			self.frames.pop();
 		}
 		
+		/// Register the positions of spread arguments, and return their index in the code, if any.
+		fn compile_spread_arguments( &mut self, spread_arguments: &[uint] ) -> Option<uint> {
+			
+			if spread_arguments.len() == 0 {
+				return None;
+			}
+			
+			self.code.spreads.push( Vec::from_slice( spread_arguments ) );
+			Some( self.code.spreads.len() - 1 )
+		}
+		
+		/// Push the values of keyword arguments, and return the index of their names in the code, if any.
+		fn compile_keyword_arguments( &mut self, keyword_arguments: &mut [node::KeywordArgument] ) -> Option<uint> {
+			
+			if keyword_arguments.len() == 0 {
+				return None;
+			}
+			
+			let mut names = Vec::new();
+			for keyword_argument in keyword_arguments.mut_iter() {
+				self.compile_expression( keyword_argument.value );
+				names.push( keyword_argument.name );
+			}
+			
+			self.code.keywords.push( names );
+			Some( self.code.keywords.len() - 1 )
+		}
+		
+		/// Return the value on top of the data stack, checking it against the declared return type.
+		fn compile_return( &mut self ) {
+			
+			match self.get_current_frame().return_type {
+				Some( variable ) => {
+					self.code.opcodes.push( opcode::Duplicate );
+					self.compile_variable_load( variable );
+					self.code.opcodes.push( opcode::Is );
+					self.code.opcodes.push( opcode::TypeCheckReturn );
+				}
+				None => {}
+			}
+			
+			self.code.opcodes.push( opcode::Return );
+		}
+		
+		fn push_loop( &mut self, continue_instruction: uint ) {
+			self.loops.push( Loop {
+				n_flow_points: self.n_flow_points,
+				continue_instruction: continue_instruction,
+				breaks: Vec::new(),
+			} );
+		}
+		
+		/// Point the loop's `break` statements at the current instruction.
+		fn pop_loop( &mut self ) {
+			let loop_ = self.loops.pop().unwrap();
+			for &(placeholder, n_flow_points) in loop_.breaks.iter() {
+				let opcode = flow_jump( n_flow_points, self.code.opcodes.len() );
+				self.fill_in_placeholder( placeholder, opcode );
+			}
+		}
+		
+		/// Store the value on top of the data stack in a newly declared variable.
+		fn compile_variable_initialization( &mut self, variable: Raw<annotation::Variable> ) {
+			match variable.local_storage_type {
+				annotation::storage::Local => {
+					self.code.opcodes.push(
+						opcode::StoreLocal( variable.local_storage_index )
+					);
+				}
+				annotation::storage::SharedLocal => {
+					self.code.opcodes.push(
+						opcode::InitializeSharedLocal( variable.local_storage_index )
+					);
+					self.code.opcodes.push(
+						opcode::StoreSharedLocal( variable.local_storage_index )
+					);
+				}
+			};
+		}
+		
+		fn compile_variable_load( &mut self, variable: Raw<annotation::Variable> ) {
+			
+			if variable.declared_in == self.get_current_frame() {
+				
+				match variable.local_storage_type {
+					annotation::storage::Local => {
+						self.code.opcodes.push(
+							opcode::LoadLocal( variable.local_storage_index )
+						);
+					}
+					annotation::storage::SharedLocal => {
+						self.code.opcodes.push(
+							opcode::LoadSharedLocal( variable.local_storage_index )
+						);
+					}
+				}
+				
+			} else {
+				
+				let bound_storage_index = self.find_bound_storage_index( variable );
+				
+				match variable.bound_storage_type {
+					annotation::storage::StaticBound => {
+						self.code.opcodes.push(
+							opcode::LoadStaticBound( bound_storage_index )
+						);
+					}
+					annotation::storage::SharedBound => {
+						self.code.opcodes.push(
+							opcode::LoadSharedBound( bound_storage_index )
+						);
+					}
+				};
+			}
+		}
+		
+		fn compile_variable_store( &mut self, variable: Raw<annotation::Variable> ) {
+			
+			if variable.declared_in == self.get_current_frame() {
+				
+				match variable.local_storage_type {
+					annotation::storage::Local => {
+						self.code.opcodes.push(
+							opcode::StoreLocal( variable.local_storage_index )
+						);
+					}
+					annotation::storage::SharedLocal => {
+						self.code.opcodes.push(
+							opcode::StoreSharedLocal( variable.local_storage_index )
+						);
+					}
+				};
+				
+			} else {
+				
+				let bound_storage_index = self.find_bound_storage_index( variable );
+				
+				match variable.bound_storage_type {
+					annotation::storage::StaticBound => {
+						self.code.opcodes.push(
+							opcode::StoreStaticBound( bound_storage_index )
+						);
+					}
+					annotation::storage::SharedBound => {
+						self.code.opcodes.push(
+							opcode::StoreSharedBound( bound_storage_index )
+						);
+					}
+				};
+			}
+		}
+		
 		fn compile_statement( &mut self, statement: &mut node::Statement ) {
 			match *statement {
 				
@@ -161,41 +374,8 @@ struct Compilation {
 							annotation: variable,
 							source_offset: _,
 						} => {
-							
 							self.compile_expression( *rvalue );
-							
-							if variable.declared_in == self.get_current_frame() {
-								
-								match variable.local_storage_type {
-									annotation::storage::Local => {
-										self.code.opcodes.push(
-											opcode::StoreLocal( variable.local_storage_index )
-										);
-									}
-									annotation::storage::SharedLocal => {
-										self.code.opcodes.push(
-											opcode::StoreSharedLocal( variable.local_storage_index )
-										);
-									}
-								};
-								
-							} else {
-								
-								let bound_storage_index = self.find_bound_storage_index( variable );
-								
-								match variable.bound_storage_type {
-									annotation::storage::StaticBound => {
-										self.code.opcodes.push(
-											opcode::StoreStaticBound( bound_storage_index )
-										);
-									}
-									annotation::storage::SharedBound => {
-										self.code.opcodes.push(
-											opcode::StoreSharedBound( bound_storage_index )
-										);
-									}
-								};
-							}
+							self.compile_variable_store( variable );
 						}
 						
 						node::DotAccessLvalue {
@@ -205,6 +385,76 @@ struct Compilation {
 							self.compile_expression( *expression );
 							self.compile_expression( *rvalue );
 							self.code.opcodes.push( opcode::SetProperty { name: name } );
+							self.code.opcodes.push( opcode::Pop );
+						}
+						
+						node::ItemAccessLvalue {
+							expression: ref mut expression,
+							key_expression: ref mut key_expression,
+						} => {
+							self.compile_expression( *expression );
+							self.compile_expression( *key_expression );
+							self.compile_expression( *rvalue );
+							self.code.opcodes.push( opcode::SetItem );
+							self.code.opcodes.push( opcode::Pop );
+						}
+					}
+				}
+				
+				node::AugmentedAssignment {
+					lvalue: ref mut lvalue,
+					operator: operator,
+					rvalue: ref mut rvalue,
+				} => {
+					
+					let operation = match operator {
+						node::AddOperator => opcode::Add,
+						node::SubtractOperator => opcode::Subtract,
+						node::MultiplyOperator => opcode::Multiply,
+						node::DivideOperator => opcode::Divide,
+						node::ModuloOperator => opcode::Modulo,
+					};
+					
+					match **lvalue {
+						
+						node::VariableLvalue {
+							name: _,
+							annotation: variable,
+							source_offset: _,
+						} => {
+							self.compile_variable_load( variable );
+							self.compile_expression( *rvalue );
+							self.code.opcodes.push( operation );
+							self.compile_variable_store( variable );
+						}
+						
+						node::DotAccessLvalue {
+							expression: ref mut expression,
+							name: name,
+						} => {
+							// the accessed expression is evaluated only once
+							self.compile_expression( *expression );
+							self.code.opcodes.push( opcode::Duplicate );
+							self.code.opcodes.push( opcode::GetProperty { name: name } );
+							self.compile_expression( *rvalue );
+							self.code.opcodes.push( operation );
+							self.code.opcodes.push( opcode::SetProperty { name: name } );
+							self.code.opcodes.push( opcode::Pop );
+						}
+						
+						node::ItemAccessLvalue {
+							expression: ref mut expression,
+							key_expression: ref mut key_expression,
+						} => {
+							// the accessed expression and the key are evaluated only once
+							self.compile_expression( *expression );
+							self.compile_expression( *key_expression );
+							self.code.opcodes.push( opcode::DuplicateTwo );
+							self.code.opcodes.push( opcode::GetItem );
+							self.compile_expression( *rvalue );
+							self.code.opcodes.push( operation );
+							self.code.opcodes.push( opcode::SetItem );
+							self.code.opcodes.push( opcode::Pop );
 						}
 					}
 				}
@@ -219,22 +469,7 @@ struct Compilation {
 					if default.is_some() {
 						
 						self.compile_expression( *default.as_mut().unwrap() );
-						
-						match annotation.local_storage_type {
-							annotation::storage::Local => {
-								self.code.opcodes.push(
-									opcode::StoreLocal( annotation.local_storage_index )
-								);
-							}
-							annotation::storage::SharedLocal => {
-								self.code.opcodes.push(
-									opcode::InitializeSharedLocal( annotation.local_storage_index )
-								);
-								self.code.opcodes.push(
-									opcode::StoreSharedLocal( annotation.local_storage_index )
-								);
-							}
-						};
+						self.compile_variable_initialization( *annotation );
 						
 					} else {
 						
@@ -268,7 +503,7 @@ struct Compilation {
 							self.code.opcodes.push( opcode::PushNothing );
 						}
 					};
-					self.code.opcodes.push( opcode::Return );
+					self.compile_return();
 				}
 				
 				node::Throw {
@@ -278,6 +513,20 @@ struct Compilation {
 					self.code.opcodes.push( opcode::Throw );
 				}
 				
+				node::Break { source_offset: _ } => {
+					let n_flow_points = self.n_flow_points - self.loops.last().unwrap().n_flow_points;
+					let placeholder = self.create_placeholder();
+					self.loops.mut_last().unwrap().breaks.push( (placeholder, n_flow_points) );
+				}
+				
+				node::Continue { source_offset: _ } => {
+					let opcode = {
+						let loop_ = self.loops.last().unwrap();
+						flow_jump( self.n_flow_points - loop_.n_flow_points, loop_.continue_instruction )
+					};
+					self.code.opcodes.push( opcode );
+				}
+				
 				node::If {
 					test: ref mut test,
 					block: ref mut if_block,
@@ -348,6 +597,8 @@ struct Compilation {
 					self.compile_expression( *while_test );
 					let test_opcode = self.create_placeholder();
 					
+					self.push_loop( start );
+					
 					for statement in while_block.mut_iter() {
 						self.compile_statement( *statement );
 					}
@@ -357,8 +608,68 @@ struct Compilation {
 					let jump = opcode::JumpIfPopFalsy { instruction: self.code.opcodes.len() };
 					self.fill_in_placeholder( test_opcode, jump );
 					
-					if else_clause.is_some() {
-						unimplemented!();
+					// the else clause is skipped by `break`
+					match *else_clause {
+						Some( ref mut else_clause ) => {
+							for statement in else_clause.block.mut_iter() {
+								self.compile_statement( *statement );
+							}
+						}
+						None => {}
+					}
+					
+					self.pop_loop();
+				}
+				
+				node::For {
+					variable_name: _,
+					variable: variable,
+					iterable: ref mut iterable,
+					block: ref mut for_block,
+					else_clause: ref mut else_clause,
+				} => {
+					
+					self.compile_expression( *iterable );
+					self.code.opcodes.push( opcode::GetIterator );
+					
+					let start = self.code.opcodes.len();
+					
+					let iterate_opcode = self.create_placeholder();
+					let check_opcode = self.create_placeholder();
+					
+					self.compile_variable_initialization( variable );
+					
+					self.push_loop( start );
+					
+					for statement in for_block.mut_iter() {
+						self.compile_statement( *statement );
+					}
+					
+					self.code.opcodes.push( opcode::Jump { instruction: start } );
+					
+					let end = self.code.opcodes.len();
+					self.fill_in_placeholder( iterate_opcode, opcode::Iterate { instruction: end } );
+					self.fill_in_placeholder( check_opcode, opcode::JumpIfIterationEnded { instruction: end } );
+					
+					// the iterator is popped once it is exhausted
+					match *else_clause {
+						Some( ref mut else_clause ) => {
+							for statement in else_clause.block.mut_iter() {
+								self.compile_statement( *statement );
+							}
+						}
+						None => {}
+					}
+					
+					// ...but `break` leaves it on the data stack
+					if self.loops.last().unwrap().breaks.len() > 0 {
+						let skip_pop = self.create_placeholder();
+						self.pop_loop();
+						self.code.opcodes.push( opcode::Pop );
+						let jump = opcode::Jump { instruction: self.code.opcodes.len() };
+						self.fill_in_placeholder( skip_pop, jump );
+					} else {
+						self.pop_loop();
 					}
 				}
 				
@@ -376,12 +687,14 @@ struct Compilation {
 					// TRY
 					
 					let push_finally = if has_finally_clause {
+						self.n_flow_points += 1;
 						Some( self.create_placeholder() )
 					} else {
 						None
 					};
 					
 					let push_catch = if has_catch_clauses {
+						self.n_flow_points += 1;
 						Some( self.create_placeholder() )
 					} else {
 						None
@@ -395,7 +708,9 @@ struct Compilation {
 					
 					if has_catch_clauses {
 						
+						// the catch flow point is also gone when a catch clause runs
 						self.code.opcodes.push( opcode::PopFlowPoint );
+						self.n_flow_points -= 1;
 						
 						let end_try_jump = self.create_placeholder();
 						
@@ -487,6 +802,7 @@ struct Compilation {
 						}
 						
 						self.code.opcodes.push( opcode::EndFinally );
+						self.n_flow_points -= 1;
 					}
 				}
 			}
@@ -511,6 +827,13 @@ struct Compilation {
 					self.code.opcodes.push( opcode::PushInteger { value: i } );
 				}
 				
+				node::BigInteger {
+					value: ref value,
+				} => {
+					self.code.opcodes.push( opcode::PushBigInteger { index: self.code.big_integers.len() } );
+					self.code.big_integers.push( Rc::new( value.clone() ) );
+				}
+				
 				node::Float {
 					value: f,
 				} => {
@@ -524,44 +847,68 @@ struct Compilation {
 					self.code.strings.push( Rc::new( value.clone() ) );
 				}
 				
-				node::Variable {
-					name: _,
-					annotation: variable,
-					source_offset: _,
+				node::InterpolatedString {
+					parts: ref mut parts,
 				} => {
-					
-					if variable.declared_in == self.get_current_frame() {
-						
-						match variable.local_storage_type {
-							annotation::storage::Local => {
-								self.code.opcodes.push(
-									opcode::LoadLocal( variable.local_storage_index )
-								);
-							}
-							annotation::storage::SharedLocal => {
-								self.code.opcodes.push(
-									opcode::LoadSharedLocal( variable.local_storage_index )
-								);
-							}
-						}
+					for (i, part) in parts.mut_iter().enumerate() {
 						
-					} else {
+						let is_string = match **part {
+							node::String {..} => true,
+							_ => false,
+						};
 						
-						let bound_storage_index = self.find_bound_storage_index( variable );
+						self.compile_expression( *part );
 						
-						match variable.bound_storage_type {
-							annotation::storage::StaticBound => {
-								self.code.opcodes.push(
-									opcode::LoadStaticBound( bound_storage_index )
-								);
-							}
-							annotation::storage::SharedBound => {
-								self.code.opcodes.push(
-									opcode::LoadSharedBound( bound_storage_index )
-								);
-							}
-						};
+						if ! is_string {
+							self.code.opcodes.push( opcode::ToString );
+						}
+						if i > 0 {
+							self.code.opcodes.push( opcode::Add );
+						}
+					}
+				}
+				
+				node::List {
+					items: ref mut items,
+				} => {
+					for item in items.mut_iter() {
+						self.compile_expression( *item );
+					}
+					self.code.opcodes.push( opcode::PushList { n_items: items.len() } );
+				}
+				
+				node::Map {
+					entries: ref mut entries,
+				} => {
+					for &(ref mut key, ref mut value) in entries.mut_iter() {
+						self.compile_expression( *key );
+						self.compile_expression( *value );
 					}
+					self.code.opcodes.push( opcode::PushMap { n_items: entries.len() } );
+				}
+				
+				node::Variable {
+					name: _,
+					annotation: variable,
+					source_offset: _,
+				} => {
+					self.compile_variable_load( variable );
+				}
+				
+				node::This {
+					annotation: variable,
+					source_offset: _,
+				} => {
+					self.compile_variable_load( variable );
+				}
+				
+				node::Super {
+					name: name,
+					annotation: variable,
+					source_offset: _,
+				} => {
+					self.compile_variable_load( variable );
+					self.code.opcodes.push( opcode::GetSuperProperty { name: name } );
 				}
 				
 				node::Name {
@@ -596,9 +943,36 @@ struct Compilation {
 					self.code.opcodes.push( opcode::GetItem );
 				}
 				
+				node::New {
+					expression: ref mut expression,
+					arguments: ref mut arguments,
+					spread_arguments: ref spread_arguments,
+					keyword_arguments: ref mut keyword_arguments,
+				} => {
+					
+					self.compile_expression( *expression );
+					
+					for argument in arguments.mut_iter() {
+						self.compile_expression( *argument );
+					}
+					
+					let spread = self.compile_spread_arguments( spread_arguments.as_slice() );
+					let keywords = self.compile_keyword_arguments( keyword_arguments.as_mut_slice() );
+					
+					self.code.opcodes.push( opcode::New {
+						n_arguments: arguments.len(),
+						spread: spread,
+						keywords: keywords,
+					} );
+					// discard the return value of `init`
+					self.code.opcodes.push( opcode::Pop );
+				}
+				
 				node::Call {
 					expression: ref mut expression,
 					arguments: ref mut arguments,
+					spread_arguments: ref spread_arguments,
+					keyword_arguments: ref mut keyword_arguments,
 				} => {
 					
 					self.compile_expression( *expression );
@@ -607,7 +981,14 @@ struct Compilation {
 						self.compile_expression( *argument );
 					}
 					
-					self.code.opcodes.push( opcode::Call { n_arguments: arguments.len() } );
+					let spread = self.compile_spread_arguments( spread_arguments.as_slice() );
+					let keywords = self.compile_keyword_arguments( keyword_arguments.as_mut_slice() );
+					
+					self.code.opcodes.push( opcode::Call {
+						n_arguments: arguments.len(),
+						spread: spread,
+						keywords: keywords,
+					} );
 				}
 				
 				node::Addition {
@@ -646,6 +1027,15 @@ struct Compilation {
 					self.code.opcodes.push( opcode::Divide );
 				}
 				
+				node::Modulo {
+					left: ref mut left,
+					right: ref mut right,
+				} => {
+					self.compile_expression( *left );
+					self.compile_expression( *right );
+					self.code.opcodes.push( opcode::Modulo );
+				}
+				
 				node::Union {
 					left: ref mut left,
 					right: ref mut right,
@@ -732,7 +1122,8 @@ struct Compilation {
 					self.compile_expression( *left );
 					let placeholder = self.create_placeholder();
 					self.compile_expression( *right );
-					self.fill_in_placeholder( placeholder, opcode::ShortCircuitAnd );
+					let jump = opcode::ShortCircuitAnd { instruction: self.code.opcodes.len() };
+					self.fill_in_placeholder( placeholder, jump );
 				}
 				
 				node::Or {
@@ -742,38 +1133,91 @@ struct Compilation {
 					self.compile_expression( *left );
 					let placeholder = self.create_placeholder();
 					self.compile_expression( *right );
-					self.fill_in_placeholder( placeholder, opcode::ShortCircuitOr );
+					let jump = opcode::ShortCircuitOr { instruction: self.code.opcodes.len() };
+					self.fill_in_placeholder( placeholder, jump );
+				}
+				
+				node::Class {
+					bases: ref mut bases,
+					properties: ref mut properties,
+					methods: ref mut methods,
+				} => {
+					
+					for base in bases.mut_iter() {
+						self.compile_expression( *base );
+					}
+					
+					for property in properties.mut_iter() {
+						match property.default {
+							Some( ref mut expression ) => {
+								self.compile_expression( *expression );
+							}
+							None => {
+								self.code.opcodes.push( opcode::PushNothing );
+							}
+						};
+					}
+					
+					for method in methods.mut_iter() {
+						self.compile_expression( method.function );
+					}
+					
+					let definition = Rc::new( class::ClassDefinition::new(
+						bases.len(),
+						properties.iter().map( |p| { p.name } ).collect(),
+						methods.iter().map( |m| { m.name } ).collect()
+					) );
+					
+					self.code.opcodes.push( opcode::PushClass { index: self.code.classes.len() } );
+					self.code.classes.push( definition );
 				}
 				
 				node::Function {
-					parameters: ref parameters,
+					parameters: ref mut parameters,
+					return_type: ref mut return_type,
 					frame: ref frame,
 					block: ref mut block,
+					is_method: _,
 				} => {
 					
 					let mut compilation = Compilation::new();
-					compilation.compile_function( frame, block.as_mut_slice() );
+					compilation.compile_function( frame, parameters.as_mut_slice(), return_type, block.as_mut_slice() );
 					let code = compilation.code;
 					
 					let mut parameter_definitions = Vec::<function::FunctionParameterDefinition>::new();
+					let mut rest = None;
 					for parameter in parameters.iter() {
 						let variable = parameter.variable;
-						match variable.local_storage_type {
+						let storage = match variable.local_storage_type {
 							annotation::storage::Local => {
-								parameter_definitions.push( function::FunctionParameterDefinition {
-									name: variable.name,
-									storage: function::LocalFunctionParameterStorage( variable.local_storage_index ),
-								} );
+								function::LocalFunctionParameterStorage( variable.local_storage_index )
 							}
 							annotation::storage::SharedLocal => {
-								parameter_definitions.push( function::FunctionParameterDefinition {
-									name: variable.name,
-									storage: function::SharedLocalFunctionParameterStorage( variable.local_storage_index ),
-								} );
+								function::SharedLocalFunctionParameterStorage( variable.local_storage_index )
 							}
 						};
+						if parameter.is_rest {
+							rest = Some( storage );
+						} else {
+							parameter_definitions.push( function::FunctionParameterDefinition {
+								name: variable.name,
+								storage: storage,
+								has_default: parameter.default.is_some(),
+							} );
+						}
 					}
 					
+					let receiver = frame.this.map( |variable| {
+						match variable.local_storage_type {
+							annotation::storage::Local => {
+								function::LocalFunctionParameterStorage( variable.local_storage_index )
+							}
+							annotation::storage::SharedLocal => {
+								function::SharedLocalFunctionParameterStorage( variable.local_storage_index )
+							}
+						}
+					} );
+					
 					let mut binding_definitions = Vec::<function::FunctionBindingDefinition>::new();
 					for binding in frame.closure.as_ref().unwrap().bindings.iter() {
 						let variable = binding.variable;
@@ -821,6 +1265,8 @@ struct Compilation {
 					let definition = Rc::new( function::FunctionDefinition::new(
 						code,
 						parameter_definitions,
+						rest,
+						receiver,
 						binding_definitions
 					) );
 					
@@ -830,3 +1276,19 @@ struct Compilation {
 			}
 		}
 	}
+
+struct Loop {
+	n_flow_points: uint,
+	continue_instruction: uint,
+	/// Placeholders for the loop's `break` statements, with the number of flow points to pop.
+	breaks: Vec<(Placeholder, uint)>,
+}
+
+/// Jump to an instruction, first popping the given number of flow points.
+fn flow_jump( n_flow_points: uint, instruction: uint ) -> opcode::OpCode {
+	if n_flow_points > 0 {
+		opcode::FlowJump { n_flow_points: n_flow_points, instruction: instruction }
+	} else {
+		opcode::Jump { instruction: instruction }
+	}
+}
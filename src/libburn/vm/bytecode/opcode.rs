@@ -21,10 +21,18 @@ pub enum OpCode {
 	JumpIfPopFalsy { pub instruction: uint },
 	FlowJump { pub n_flow_points: uint, pub instruction: uint },
 	
+	// Iteration
+	GetIterator,
+	Iterate { pub instruction: uint },
+	JumpIfIterationEnded { pub instruction: uint },
+	
 	// Function flow
-	Call { pub n_arguments: uint },
-	TypeCheckLocal { pub index: uint },
-	TypeCheckSharedLocal { pub index: uint },
+	Call { pub n_arguments: uint, pub spread: Option<uint>, pub keywords: Option<uint> },
+	New { pub n_arguments: uint, pub spread: Option<uint>, pub keywords: Option<uint> },
+	TypeCheckLocal { pub index: uint, pub name: Identifier },
+	TypeCheckSharedLocal { pub index: uint, pub name: Identifier },
+	TypeCheckReturn,
+	JumpIfArgumentGiven { pub parameter: uint, pub instruction: uint },
 	Return,
 	ReturnNothing,
 	
@@ -43,14 +51,20 @@ pub enum OpCode {
 	
 	// Data stack operations
 	Pop,
+	Duplicate,
+	DuplicateTwo,
 	
 	// Values
 	PushFunction { pub index: uint },
+	PushClass { pub index: uint },
 	PushString { pub index: uint },
 	PushFloat { pub value: f64 },
 	PushInteger { pub value: i64 },
+	PushBigInteger { pub index: uint },
 	PushBoolean { pub value: bool },
 	PushNothing,
+	PushList { pub n_items: uint },
+	PushMap { pub n_items: uint },
 	InlinedModule { pub ptr: Raw<::lang::module::Module> },
 	
 	// Variables
@@ -70,8 +84,10 @@ pub enum OpCode {
 	
 	// Access
 	GetProperty { pub name: Identifier },
+	GetSuperProperty { pub name: Identifier },
 	SetProperty { pub name: Identifier },
 	GetItem,
+	SetItem,
 	
 	// Operations
 	Is,
@@ -86,7 +102,8 @@ pub enum OpCode {
 	Subtract,
 	Multiply,
 	Divide,
+	Modulo,
 	Not,
-	ShortCircuitAnd,
-	ShortCircuitOr,
+	ShortCircuitAnd { pub instruction: uint },
+	ShortCircuitOr { pub instruction: uint },
 }
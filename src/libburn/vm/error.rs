@@ -7,18 +7,22 @@ pub trait Error {
 	fn get_message<'l>( &'l self ) -> &'l str;
 	fn get_origin<'l>( &'l self ) -> &'l Origin;
 	fn get_source_offset( &self ) -> uint;
+	/// The number of characters the error spans, starting at its offset.
+	fn get_source_length( &self ) -> uint { 1 }
 }
 
 pub struct ParseError {
 	pub message: String,
 	pub origin: Rc<Box<Origin>>,
 	pub source_offset: uint,
+	pub source_length: uint,
 }
 
 	impl Error for ParseError {
 		fn get_message<'l>( &'l self ) -> &'l str { self.message.as_slice() }
 		fn get_origin<'l>( &'l self ) -> &'l Origin { let tmp: &Origin = *self.origin; tmp }
 		fn get_source_offset( &self ) -> uint { self.source_offset }
+		fn get_source_length( &self ) -> uint { self.source_length }
 	}
 
 pub struct AnalysisError {
@@ -2,6 +2,11 @@ use std::mem;
 use mem::rc::Rc;
 use lang::value;
 use lang::function;
+use lang::class::Class;
+use lang::object::Object;
+use lang::list::List;
+use lang::map::Map;
+use lang::identifier::Identifier;
 use lang::operations;
 use vm::bytecode::opcode;
 use vm::virtual_machine::VirtualMachine;
@@ -47,62 +52,69 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 	
 	if fiber.frame.is_rust_operation() {
 		
-		match fiber.flow.clone() { // optimize! get rid of this clone
+		// the operation is either started, or resumed with the result of a frame it pushed
+		let input = match fiber.replace_flow( flow::Running ) {
+			flow::Running => Ok( value::Nothing ),
+			flow::Returning( v ) => Ok( v ),
+			flow::Throwing( v ) => Err( v ),
+			_ => { unreachable!() },
+		};
+		
+		match fiber.frame.get_rust_operation().run( vm, input ) {
 			
-			flow::Running => {
-				
-				let result = match fiber.replace_flow( flow::Running ) {
-					flow::Running => Ok( value::Nothing ),
-					flow::Returning( v ) => Ok( v ),
-					flow::Throwing( v ) => Err( v ),
-					_ => { unreachable!() },
-				};
-				
-				match fiber.frame.get_rust_operation().run( vm, result ) {
+			rust::Ok( value ) => {
+				match fiber.flow_points.pop().unwrap() {
 					
-					rust::Ok( value ) => {
-						fiber.set_flow( flow::Returning( value ) );
-					}
-					
-					rust::Throw( value ) => {
-						fiber.set_flow( flow::Throwing( value ) );
+					flow::PopFrame { data_stack_len: n } => {
+						fiber.pop_frame();
+						fiber.data_stack.truncate( n );
+						if fiber.frame.is_rust_operation() {
+							fiber.set_flow( flow::Returning( value ) );
+						} else {
+							fiber.push_data( value );
+						}
 					}
 					
-					rust::Burn( frame ) => {
-						fiber.flow_points.push( flow::PopFrame { data_stack_len: fiber.data_stack.len() } );
-						new_frame!( frame );
+					flow::PopFrameAndRestoreFlow { data_stack_len: n } => {
+						fiber.pop_frame();
+						fiber.data_stack.truncate( n );
+						fiber.push_data( value );
+						fiber.restore_flow();
 					}
 					
-					_ => { unimplemented!(); }
+					_ => { fail!(); }
 				}
 			}
 			
-			flow::Returning( value ) => {
-				loop {
-					match fiber.flow_points.pop().unwrap() {
-						
-						flow::PopFrame { data_stack_len: n } => {
-							fiber.pop_frame();
-							fiber.data_stack.truncate( n );
-							fiber.push_data( value );
-							fiber.set_flow( flow::Running );
-							continue 'frame_loop;
-						}
-						
-						flow::PopFrameAndRestoreFlow { data_stack_len: n } => {
-							fiber.pop_frame();
-							fiber.data_stack.truncate( n );
-							fiber.push_data( value );
-							fiber.restore_flow();
-							continue 'frame_loop;
-						}
-						
-						_ => { fail!(); }
+			rust::Throw( value ) => {
+				match fiber.flow_points.pop().unwrap() {
+					
+					flow::PopFrame { data_stack_len: n } => {
+						fiber.pop_frame();
+						fiber.data_stack.truncate( n );
+					}
+					
+					flow::PopFrameAndRestoreFlow { data_stack_len: n } => {
+						fiber.pop_frame();
+						fiber.data_stack.truncate( n );
+						fiber.suppressed_flows.pop();
 					}
+					
+					_ => { fail!(); }
 				}
+				
+				fiber.set_flow( flow::Throwing( value ) );
 			}
 			
-			_ => { fail!(); }
+			rust::Burn( frame ) => {
+				new_frame!( frame );
+			}
+			
+			rust::Rust( operation ) => {
+				new_frame!( frame::RustOperationFrame( operation ) );
+			}
+			
+			_ => { unimplemented!(); }
 		}
 		
 	} else { // not a rust-type frame
@@ -133,14 +145,80 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 						}}
 					)
 					
+					macro_rules! call_function (
+						( $function:expr, $receiver:expr, $arguments:expr, $keyword_arguments:expr ) => {{
+							match frame::Frame::new_function_call( vm, $function, $receiver, $arguments, $keyword_arguments ) {
+								Ok( frame ) => {
+									fiber.frame.get_context().instruction += 1;
+									new_frame!( frame );
+								}
+								Err( throwable ) => { throw!( throwable ); }
+							}
+						}}
+					)
+					
+					// binds the arguments straight from the data stack, for calls without spread or keyword arguments
+					macro_rules! call_function_from_stack (
+						( $function:expr, $receiver:expr, $n_arguments:expr ) => {{
+							match frame::Frame::new_function_call_from_stack( vm, $function, $receiver, &mut fiber.data_stack, $n_arguments ) {
+								Ok( frame ) => {
+									fiber.frame.get_context().instruction += 1;
+									new_frame!( frame );
+								}
+								Err( throwable ) => { throw!( throwable ); }
+							}
+						}}
+					)
+					
+					// pops keyword arguments, then positional ones
+					macro_rules! pop_arguments (
+						( $n_arguments:expr, $keywords:expr ) => {{
+							let keyword_arguments = match $keywords {
+								Some( k ) => {
+									let n_keyword_arguments = fiber.frame.get_code().keywords.get( k ).len();
+									let values = fiber.pop_data_n( n_keyword_arguments );
+									let names = fiber.frame.get_code().keywords.get( k );
+									names.iter().map( |name| { *name } ).zip( values.move_iter() ).collect::<Vec<(Identifier, value::Value)>>()
+								}
+								None => Vec::new(),
+							};
+							let arguments = fiber.pop_data_n( $n_arguments );
+							(arguments, keyword_arguments)
+						}}
+					)
+					
+					// expands the arguments at the given positions, which have to be lists
+					macro_rules! spread_arguments (
+						( $arguments:expr, $spread:expr ) => {{
+							match $spread {
+								Some( s ) => {
+									let spread = {
+										let positions = fiber.frame.get_code().spreads.get( s ).as_slice();
+										function::spread_arguments( $arguments, positions )
+									};
+									match spread {
+										Ok( arguments ) => arguments,
+										Err( throwable ) => { throw!( throwable ); }
+									}
+								}
+								None => $arguments,
+							}
+						}}
+					)
+					
 					macro_rules! handle_operation_result (
 						( $operation:expr ) => {{
 							match $operation {
 								rust::Ok( result ) => { fiber.push_data( result ); }
 								rust::Throw( t ) => { throw!( t ); }
 								rust::Burn( frame ) => {
+									fiber.frame.get_context().instruction += 1;
 									new_frame!( frame );
 								}
+								rust::Rust( operation ) => {
+									fiber.frame.get_context().instruction += 1;
+									new_frame!( frame::RustOperationFrame( operation ) );
+								}
 								_ => { unimplemented!(); }
 							};
 						}}
@@ -192,60 +270,199 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 							continue 'flow_loop;
 						}
 						
+						// Iteration
+						
+						opcode::GetIterator => {
+							handle_operation_result!( operations::iterate( &fiber.pop_data() ) );
+						}
+						
+						opcode::Iterate { instruction: i } => {
+							
+							let iterator = fiber.data_stack.last().unwrap().clone();
+							
+							match iterator {
+								
+								value::RcSpecial( mut r ) => {
+									match r.next() {
+										Some( value ) => {
+											fiber.push_data( value );
+										}
+										None => {
+											fiber.pop_data();
+											fiber.frame.get_context().instruction = i;
+											continue 'instruction_loop;
+										}
+									};
+								}
+								
+								value::Object( ref object ) => {
+									// the result is checked by the following JumpIfIterationEnded
+									let done = object.class.find_method( Identifier::find_or_create( "done" ) ).unwrap();
+									call_function!( done, Some( iterator.clone() ), Vec::new(), Vec::new() );
+								}
+								
+								_ => { unreachable!(); }
+							};
+						}
+						
+						opcode::JumpIfIterationEnded { instruction: i } => {
+							
+							// only iterators implemented in burn are asked whether they are done, before getting their next item
+							let is_object = match *fiber.data_stack.get( fiber.data_stack.len() - 2 ) {
+								value::Object(..) => true,
+								_ => false,
+							};
+							
+							if is_object {
+								
+								if operations::is_truthy( &fiber.pop_data() ) {
+									fiber.pop_data();
+									fiber.frame.get_context().instruction = i;
+									continue 'instruction_loop;
+								}
+								
+								let iterator = fiber.data_stack.last().unwrap().clone();
+								let next = match iterator {
+									value::Object( ref object ) => object.class.find_method( Identifier::find_or_create( "next" ) ).unwrap(),
+									_ => { unreachable!(); }
+								};
+								call_function!( next, Some( iterator ), Vec::new(), Vec::new() );
+							}
+						}
+						
 						// Functions
 						
-						opcode::Call { n_arguments: n_arguments } => {
+						opcode::Call { n_arguments: n_arguments, spread: spread, keywords: keywords } => {
 							
-							let function_offset = fiber.data_stack.len() - n_arguments - 1;
-							// optimize! could use an unsafe copy here, with an unsafe set_len later
-							let function = mem::replace( fiber.data_stack.get_mut( function_offset ), value::Nothing );
+							if spread.is_none() && keywords.is_none() {
+								
+								let offset = fiber.data_stack.len() - n_arguments - 1;
+								let is_function = match *fiber.data_stack.get( offset ) {
+									value::Function(..) | value::BoundMethod(..) => true,
+									_ => false,
+								};
+								
+								if is_function {
+									match fiber.data_stack.remove( offset ).unwrap() {
+										value::Function( function ) => {
+											call_function_from_stack!( function, None, n_arguments );
+										}
+										value::BoundMethod( bound_method ) => {
+											call_function_from_stack!(
+												bound_method.function.clone(),
+												Some( bound_method.receiver.clone() ),
+												n_arguments
+											);
+										}
+										_ => { unreachable!(); }
+									}
+								}
+							}
+							
+							let (arguments, keyword_arguments) = pop_arguments!( n_arguments, keywords );
+							let function = fiber.pop_data();
+							let arguments = spread_arguments!( arguments, spread );
 							
 							match function {
 								
-								value::Function( mut function ) => {
-									
-									fiber.frame.get_context().instruction += 1;
-									
-									let mut locals = Vec::from_elem(
-										function.definition.code.n_local_variables,
-										value::Nothing
-									);
-									let mut shared = Vec::from_elem(
-										function.definition.code.n_shared_local_variables,
-										None
+								value::Function( function ) => {
+									call_function!( function, None, arguments, keyword_arguments );
+								}
+								
+								value::BoundMethod( bound_method ) => {
+									call_function!(
+										bound_method.function.clone(),
+										Some( bound_method.receiver.clone() ),
+										arguments,
+										keyword_arguments
 									);
-									
-									{
-										let parameters = function.definition.parameters.as_slice();
-										assert!( n_arguments == parameters.len() );
-										for parameter in parameters.iter().rev() {
-											match parameter.storage {
-												function::LocalFunctionParameterStorage( i ) => {
-													*locals.get_mut( i ) = fiber.pop_data();
-												}
-												function::SharedLocalFunctionParameterStorage( i ) => {
-													*shared.get_mut( i ) = Some( Rc::new( fiber.pop_data() ) );
-												}
-											};
-										}
-									}
-									
-									new_frame!( frame::BurnFunctionFrame {
-										context: frame::BurnContext::new( locals, shared ),
-										function: function,
-									} );
 								}
 								
-								_ => { unimplemented!(); }
+								value::RcSpecial( ref r ) if r.is_callable() => {
+									handle_operation_result!( r.call( vm, arguments, keyword_arguments ) );
+								}
+								
+								function @ _ => {
+									let message = format!( "{} is not callable", operations::repr( &function ) );
+									throw!( errors::create_type_error( message ) );
+								}
+							}
+						}
+						
+						opcode::New { n_arguments: n_arguments, spread: spread, keywords: keywords } => {
+							
+							let n_keyword_arguments = match keywords {
+								Some( k ) => fiber.frame.get_code().keywords.get( k ).len(),
+								None => 0,
+							};
+							let class_offset = fiber.data_stack.len() - n_arguments - n_keyword_arguments - 1;
+							let class = mem::replace( fiber.data_stack.get_mut( class_offset ), value::Nothing );
+							
+							let class = match class {
+								value::Class( ref class ) => class.clone(),
+								_ => {
+									let message = format!( "Can't instantiate {}", operations::repr( &class ) );
+									throw!( errors::create_type_error( message ) );
+								}
+							};
+							
+							let init = class.find_method( Identifier::find_or_create( "init" ) );
+							
+							let object = Object::new( vm, class );
+							let object = value::Object( vm.objects.register( object ) );
+							// the object replaces the class on the data stack, and remains there after `init` returns
+							*fiber.data_stack.get_mut( class_offset ) = object.clone();
+							
+							match init {
+								
+								Some( init ) if spread.is_none() && keywords.is_none() => {
+									call_function_from_stack!( init, Some( object ), n_arguments );
+								}
+								
+								Some( init ) => {
+									let (arguments, keyword_arguments) = pop_arguments!( n_arguments, keywords );
+									let arguments = spread_arguments!( arguments, spread );
+									call_function!( init, Some( object ), arguments, keyword_arguments );
+								}
+								
+								None => {
+									if n_arguments + n_keyword_arguments > 0 {
+										let message = "Class has no `init` method and takes no arguments".to_string();
+										throw!( errors::create_argument_error( message ) );
+									}
+									fiber.push_data( value::Nothing );
+								}
+							}
+						}
+						
+						opcode::JumpIfArgumentGiven { parameter: p, instruction: i } => {
+							if fiber.frame.get_context().given_arguments & ( 1u64 << p ) != 0 {
+								fiber.frame.get_context().instruction = i;
+								continue 'instruction_loop;
 							}
 						}
 						
-						opcode::TypeCheckLocal { index: _ } => {
-							unimplemented!();
+						opcode::TypeCheckLocal { index: i, name: name } => {
+							if ! operations::is_truthy( &fiber.pop_data() ) {
+								let argument = fiber.frame.get_local_variable( i ).clone();
+								let message = format!( "Wrong type for argument ${}: {}", name, operations::repr( &argument ) );
+								throw!( errors::create_argument_error( message ) );
+							}
 						}
 						
-						opcode::TypeCheckSharedLocal { index: _ } => {
-							unimplemented!();
+						opcode::TypeCheckSharedLocal { index: i, name: name } => {
+							if ! operations::is_truthy( &fiber.pop_data() ) {
+								let argument = (**fiber.frame.get_shared_local_variable( i ).as_mut().unwrap()).clone();
+								let message = format!( "Wrong type for argument ${}: {}", name, operations::repr( &argument ) );
+								throw!( errors::create_argument_error( message ) );
+							}
+						}
+						
+						opcode::TypeCheckReturn => {
+							if ! operations::is_truthy( &fiber.pop_data() ) {
+								let message = format!( "Wrong type for return value: {}", operations::repr( fiber.data_stack.last().unwrap() ) );
+								throw!( errors::create_type_error( message ) );
+							}
 						}
 						
 						opcode::Return => {
@@ -371,6 +588,19 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 							fiber.data_stack.pop();
 						}
 						
+						opcode::Duplicate => {
+							let value = fiber.data_stack.last().unwrap().clone();
+							fiber.push_data( value );
+						}
+						
+						opcode::DuplicateTwo => {
+							let offset = fiber.data_stack.len() - 2;
+							let first = fiber.data_stack.get( offset ).clone();
+							let second = fiber.data_stack.get( offset + 1 ).clone();
+							fiber.push_data( first );
+							fiber.push_data( second );
+						}
+						
 						// Values
 						
 						opcode::PushFunction { index: i } => {
@@ -399,11 +629,73 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 								}
 							}
 							
+							// closures inside a method close over its class too, so `super` works in them
+							match fiber.frame {
+								frame::BurnFunctionFrame { function: ref enclosing, .. } => {
+									function.class = enclosing.class.clone();
+								}
+								_ => {}
+							};
+							
 							fiber.push_data(
 								value::Function( vm.functions.register( function ) )
 							);
 						}
 						
+						opcode::PushClass { index: i } => {
+							
+							let definition = fiber.frame.get_code().classes.get( i ).clone();
+							
+							let mut methods = Vec::new();
+							for &name in definition.methods.iter().rev() {
+								match fiber.pop_data() {
+									value::Function( method ) => { methods.push( (name, method) ); }
+									_ => { unreachable!(); }
+								};
+							}
+							
+							let mut properties = Vec::new();
+							for &name in definition.properties.iter().rev() {
+								let default = fiber.pop_data();
+								properties.push( (name, default) );
+							}
+							properties.reverse();
+							
+							let mut bases = Vec::new();
+							for _ in range( 0, definition.n_bases ) {
+								match fiber.pop_data() {
+									value::Class( base ) => { bases.push( base ); }
+									base @ _ => {
+										let message = format!( "Can't extend {}", operations::repr( &base ) );
+										throw!( errors::create_type_error( message ) );
+									}
+								};
+							}
+							bases.reverse();
+							
+							let mut class = match Class::new( definition, bases ) {
+								Ok( class ) => class,
+								Err( () ) => {
+									let message = "Can't create a consistent method resolution order".to_string();
+									throw!( errors::create_type_error( message ) );
+								}
+							};
+							
+							class.properties = properties;
+							for &(name, ref method) in methods.iter() {
+								class.methods.insert( name, method.clone() );
+							}
+							
+							let class = vm.classes.register( class );
+							
+							for &(_, ref method) in methods.iter() {
+								let mut method = method.clone();
+								method.class = Some( class.clone() );
+							}
+							
+							fiber.push_data( value::Class( class ) );
+						}
+						
 						opcode::PushString { index: i } => {
 							let string = fiber.frame.get_code().strings.get( i ).clone();
 							fiber.push_data( value::String( string ) );
@@ -417,6 +709,11 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 							fiber.push_data( value::Integer( i ) );
 						}
 						
+						opcode::PushBigInteger { index: i } => {
+							let big_integer = fiber.frame.get_code().big_integers.get( i ).clone();
+							fiber.push_data( value::BigInteger( big_integer ) );
+						}
+						
 						opcode::PushBoolean { value: b } => {
 							fiber.push_data( value::Boolean( b ) );
 						}
@@ -425,6 +722,28 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 							fiber.push_data( value::Nothing );
 						}
 						
+						opcode::PushList { n_items: n_items } => {
+							let items = fiber.pop_data_n( n_items );
+							let list = vm.lists.register( List::new( items ) );
+							fiber.push_data( value::List( list ) );
+						}
+						
+						opcode::PushMap { n_items: n_items } => {
+							let mut items = fiber.pop_data_n( 2 * n_items ).move_iter();
+							let mut map = Map::new();
+							loop {
+								let (key, value) = match (items.next(), items.next()) {
+									(Some( key ), Some( value )) => (key, value),
+									_ => { break; }
+								};
+								match map.set( key, value ) {
+									Ok( () ) => {}
+									Err( throwable ) => { throw!( throwable ); }
+								}
+							}
+							fiber.push_data( value::Map( vm.maps.register( map ) ) );
+						}
+						
 						opcode::InlinedModule { ptr: ptr } => {
 							fiber.push_data( value::Module( ptr ) );
 						}
@@ -503,6 +822,12 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 							handle_operation_result!( operations::get_property( &left, name ) );
 						}
 						
+						opcode::GetSuperProperty { name: name } => {
+							let this = fiber.pop_data();
+							let owner = fiber.frame.get_closure().class.clone().unwrap();
+							handle_operation_result!( operations::get_super_property( &this, &owner, name ) );
+						}
+						
 						opcode::SetProperty { name: name } => {
 							let right = fiber.pop_data();
 							let left = fiber.pop_data();
@@ -512,8 +837,14 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 						opcode::GetItem => {
 							let key = fiber.pop_data();
 							let expression = fiber.pop_data();
-							(key); (expression);
-							unimplemented!();
+							handle_operation_result!( operations::get_item( &expression, &key ) );
+						}
+						
+						opcode::SetItem => {
+							let value = fiber.pop_data();
+							let key = fiber.pop_data();
+							let expression = fiber.pop_data();
+							handle_operation_result!( operations::set_item( &expression, &key, &value ) );
 						}
 						
 						// Operators
@@ -542,6 +873,12 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 							handle_operation_result!( operations::divide( &left, &right ) );
 						}
 						
+						opcode::Modulo => {
+							let right = fiber.pop_data();
+							let left = fiber.pop_data();
+							handle_operation_result!( operations::modulo( &left, &right ) );
+						}
+						
 						opcode::Union => {
 							let right = fiber.pop_data();
 							let left = fiber.pop_data();
@@ -591,15 +928,26 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 						}
 						
 						opcode::Not => {
-							unimplemented!();
+							let value = fiber.pop_data();
+							handle_operation_result!( operations::not( &value ) );
 						}
 						
-						opcode::ShortCircuitAnd => {
-							unimplemented!();
+						// the left operand is the result if it decides the outcome
+						
+						opcode::ShortCircuitAnd { instruction: i } => {
+							if ! operations::is_truthy( fiber.data_stack.last().unwrap() ) {
+								fiber.frame.get_context().instruction = i;
+								continue 'instruction_loop;
+							}
+							fiber.pop_data();
 						}
 						
-						opcode::ShortCircuitOr => {
-							unimplemented!();
+						opcode::ShortCircuitOr { instruction: i } => {
+							if operations::is_truthy( fiber.data_stack.last().unwrap() ) {
+								fiber.frame.get_context().instruction = i;
+								continue 'instruction_loop;
+							}
+							fiber.pop_data();
 						}
 						
 					} // match opcode
@@ -621,8 +969,9 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 						}
 						
 						flow::StartFinally { instruction: i } => {
+							// the finally flow point itself has been popped already
 							fiber.suppressed_flows.push( flow::Jumping {
-								n_flow_points: n_flow_points,
+								n_flow_points: n_flow_points - 1,
 								instruction: instruction,
 							} );
 							fiber.flow_points.push( flow::PopSuppressedFlow );
@@ -643,6 +992,9 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 					n_flow_points -= 1;
 				}
 				
+				fiber.set_flow( flow::Running );
+				fiber.frame.get_context().instruction = instruction;
+				
 			} // flow::Jumping( e )
 			
 			flow::Returning( value ) => {
@@ -665,8 +1017,12 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 						flow::PopFrame { data_stack_len: n } => {
 							fiber.pop_frame();
 							fiber.data_stack.truncate( n );
-							fiber.push_data( value );
-							fiber.set_flow( flow::Running );
+							if fiber.frame.is_rust_operation() {
+								fiber.set_flow( flow::Returning( value ) );
+							} else {
+								fiber.push_data( value );
+								fiber.set_flow( flow::Running );
+							}
 							continue 'frame_loop;
 						}
 						
@@ -719,13 +1075,20 @@ pub fn run( vm: &mut VirtualMachine, mut fiber: Box<Fiber> ) {
 							continue 'frame_loop;
 						}
 						
-						flow::PopFrame { data_stack_len: n }
-						| flow::PopFrameAndRestoreFlow { data_stack_len: n } => {
+						flow::PopFrame { data_stack_len: n } => {
 							fiber.pop_frame();
 							fiber.data_stack.truncate( n );
 							continue 'frame_loop;
 						}
 						
+						flow::PopFrameAndRestoreFlow { data_stack_len: n } => {
+							fiber.pop_frame();
+							fiber.data_stack.truncate( n );
+							// the throwable replaces the suppressed flow
+							fiber.suppressed_flows.pop();
+							continue 'frame_loop;
+						}
+						
 						flow::PopSuppressedFlow => {
 							fiber.suppressed_flows.pop();
 						}
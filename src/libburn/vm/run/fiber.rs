@@ -55,6 +55,16 @@ pub struct Fiber {
 			self.data_stack.push( value );
 		}
 		
+		/// Pop the top `n` values, in the order they were pushed.
+		pub fn pop_data_n( &mut self, n: uint ) -> Vec<value::Value> {
+			let mut values = Vec::with_capacity( n );
+			for _ in range( 0, n ) {
+				values.push( self.data_stack.pop().unwrap() );
+			}
+			values.reverse();
+			values
+		}
+		
 		pub fn end_return( self, value: value::Value ) {
 			if self.on_return.is_some() {
 				self.on_return.unwrap()( value );
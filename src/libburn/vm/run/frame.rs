@@ -1,10 +1,16 @@
 use mem::rc::Rc;
 use mem::gc::Gc;
+use lang::value;
 use lang::value::Value;
 use lang::origin::Origin;
+use lang::function;
 use lang::function::Function;
+use lang::list::List;
+use lang::identifier::Identifier;
 use vm::bytecode::code::Code;
 use vm::run::rust;
+use vm::virtual_machine::VirtualMachine;
+use builtin::burn::errors::create_argument_error;
 
 pub enum Frame {
 	
@@ -24,6 +30,108 @@ pub enum Frame {
 
 	impl Frame {
 		
+		/// Create the frame for calling a Burn function with the given arguments.
+		///
+		/// Throws an `ArgumentError` if the arguments don't match the parameters,
+		/// or if an argument without a default is missing.
+		/// Extra positional arguments are collected into a list, if the function has a rest parameter.
+		pub fn new_function_call(
+			vm: &mut VirtualMachine,
+			function: Gc<Function>,
+			receiver: Option<Value>,
+			mut arguments: Vec<Value>,
+			keyword_arguments: Vec<(Identifier, Value)>
+		) -> Result<Frame,Value> {
+			
+			let n_parameters = function.definition.parameters.len();
+			let extra = if function.definition.rest.is_some() && arguments.len() > n_parameters {
+				split_off( &mut arguments, n_parameters )
+			} else {
+				Vec::new()
+			};
+			
+			let (mut locals, mut shared) = create_variables( &function );
+			let mut given_arguments = 0u64;
+			
+			{
+				let parameters = function.definition.parameters.as_slice();
+				
+				if keyword_arguments.is_empty() {
+					
+					// positional arguments are in parameter order, so there are no names to match
+					try!( function::check_argument_count( parameters.len(), arguments.len() ) );
+					let mut arguments = arguments.move_iter();
+					for (i, parameter) in parameters.iter().enumerate() {
+						try!( store_argument( parameter, i, arguments.next(), &mut locals, &mut shared, &mut given_arguments ) );
+					}
+					
+				} else {
+					
+					let names = function.definition.parameter_names.as_slice();
+					let matched = try!( function::match_arguments( names, arguments, keyword_arguments ) );
+					for (i, (parameter, argument)) in parameters.iter().zip( matched.move_iter() ).enumerate() {
+						try!( store_argument( parameter, i, argument, &mut locals, &mut shared, &mut given_arguments ) );
+					}
+				}
+			}
+			
+			Ok( create_function_frame( vm, function, receiver, extra, locals, shared, given_arguments ) )
+		}
+		
+		/// Create the frame for calling a Burn function with the top `n_arguments` values of the data stack,
+		/// for calls without spread or keyword arguments.
+		///
+		/// The arguments are moved straight into the parameters' variables.
+		/// They are popped even if the call throws, like `new_function_call` does.
+		pub fn new_function_call_from_stack(
+			vm: &mut VirtualMachine,
+			function: Gc<Function>,
+			receiver: Option<Value>,
+			data_stack: &mut Vec<Value>,
+			n_arguments: uint
+		) -> Result<Frame,Value> {
+			
+			let offset = data_stack.len() - n_arguments;
+			let n_parameters = function.definition.parameters.len();
+			
+			// check everything that can throw before binding anything
+			let n_extra = if n_arguments > n_parameters && function.definition.rest.is_some() {
+				n_arguments - n_parameters
+			} else {
+				0
+			};
+			let n_given = n_arguments - n_extra;
+			
+			let checked = function::check_argument_count( n_parameters, n_given ).and_then( |_| {
+				match function.definition.parameters.slice_from( n_given ).iter().find( |p| { ! p.has_default } ) {
+					Some( parameter ) => Err( create_argument_error( format!( "Missing argument ${}", parameter.name ) ) ),
+					None => Ok( () ),
+				}
+			} );
+			match checked {
+				Ok( () ) => {}
+				Err( throwable ) => {
+					data_stack.truncate( offset );
+					return Err( throwable );
+				}
+			}
+			
+			let extra = split_off( data_stack, offset + n_given );
+			
+			let (mut locals, mut shared) = create_variables( &function );
+			let mut given_arguments = 0u64;
+			
+			{
+				let parameters = function.definition.parameters.slice_to( n_given );
+				for (i, parameter) in parameters.iter().enumerate().rev() {
+					let argument = data_stack.pop();
+					try!( store_argument( parameter, i, argument, &mut locals, &mut shared, &mut given_arguments ) );
+				}
+			}
+			
+			Ok( create_function_frame( vm, function, receiver, extra, locals, shared, given_arguments ) )
+		}
+		
 		pub fn is_rust_operation( &self ) -> bool {
 			match *self {
 				RustOperationFrame(..) => true,
@@ -66,7 +174,7 @@ pub enum Frame {
 			self.get_context().shared_local_variables.get_mut( index )
 		}
 		
-		fn get_closure<'l>( &'l mut self ) -> &'l mut Function {
+		pub fn get_closure<'l>( &'l mut self ) -> &'l mut Function {
 			match_enum!( *self to BurnFunctionFrame { function: ref mut function, .. } => { &mut **function } )
 		}
 		
@@ -82,6 +190,98 @@ pub enum Frame {
 type Locals = Vec<Value>;
 type SharedLocals = Vec<Option<Rc<Value>>>;
 
+/// Create the function's local variables, with all parameters unset.
+fn create_variables( function: &Gc<Function> ) -> (Locals, SharedLocals) {
+	let locals = Vec::from_elem(
+		function.definition.code.n_local_variables,
+		value::Nothing
+	);
+	let shared = Vec::from_elem(
+		function.definition.code.n_shared_local_variables,
+		None
+	);
+	(locals, shared)
+}
+
+/// Create the frame once the arguments are bound, storing the extra positional arguments and the receiver.
+fn create_function_frame(
+	vm: &mut VirtualMachine,
+	function: Gc<Function>,
+	receiver: Option<Value>,
+	extra: Vec<Value>,
+	mut locals: Locals,
+	mut shared: SharedLocals,
+	given_arguments: u64
+) -> Frame {
+	
+	match function.definition.rest {
+		Some( storage ) => {
+			let list = value::List( vm.lists.register( List::new( extra ) ) );
+			store( storage, list, &mut locals, &mut shared );
+		}
+		None => {}
+	};
+	
+	match function.definition.receiver {
+		Some( storage ) => {
+			store( storage, receiver.unwrap_or( value::Nothing ), &mut locals, &mut shared );
+		}
+		None => {}
+	};
+	
+	let mut context = BurnContext::new( locals, shared );
+	context.given_arguments = given_arguments;
+	
+	BurnFunctionFrame {
+		context: context,
+		function: function,
+	}
+}
+
+/// Move the values from `at` onwards out of `values`, keeping their order.
+fn split_off( values: &mut Vec<Value>, at: uint ) -> Vec<Value> {
+	let mut split = Vec::with_capacity( values.len() - at );
+	while values.len() > at {
+		split.push( values.pop().unwrap() );
+	}
+	split.reverse();
+	split
+}
+
+/// Store the argument for a parameter in its local variable, recording whether it was given.
+///
+/// Throws an `ArgumentError` if it is missing and the parameter has no default.
+fn store_argument(
+	parameter: &function::FunctionParameterDefinition,
+	index: uint,
+	argument: Option<Value>,
+	locals: &mut Locals,
+	shared: &mut SharedLocals,
+	given_arguments: &mut u64
+) -> Result<(),Value> {
+	
+	match argument {
+		Some( argument ) => {
+			*given_arguments |= 1u64 << index;
+			store( parameter.storage, argument, locals, shared );
+			Ok( () )
+		}
+		None if parameter.has_default => Ok( () ),
+		None => Err( create_argument_error( format!( "Missing argument ${}", parameter.name ) ) ),
+	}
+}
+
+fn store( storage: function::FunctionParameterStorage, value: Value, locals: &mut Locals, shared: &mut SharedLocals ) {
+	match storage {
+		function::LocalFunctionParameterStorage( i ) => {
+			*locals.get_mut( i ) = value;
+		}
+		function::SharedLocalFunctionParameterStorage( i ) => {
+			*shared.get_mut( i ) = Some( Rc::new( value ) );
+		}
+	};
+}
+
 pub struct BurnContext {
 	// optimize! the length of *_variables is known via the type
 	// so instead of a vec, this could be a pointer to a fixed-size buffer
@@ -89,6 +289,8 @@ pub struct BurnContext {
 	// optimize! someday, rust should be able to store Option<Rc<...>> in one word
 	pub shared_local_variables: SharedLocals,
 	pub instruction: uint,
+	/// One bit per parameter, set if it was given an argument, so omitted ones can be set to their defaults.
+	pub given_arguments: u64,
 }
 
 	impl BurnContext {
@@ -98,6 +300,7 @@ pub struct BurnContext {
 				local_variables: locals,
 				shared_local_variables: shared,
 				instruction: 0,
+				given_arguments: 0,
 			}
 		}
 	}
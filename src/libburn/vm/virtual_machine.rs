@@ -6,6 +6,10 @@ use mem::rc::Rc;
 use lang::origin;
 use lang::origin::Origin;
 use lang::function::Function;
+use lang::class::Class;
+use lang::object::Object;
+use lang::list::List;
+use lang::map::Map;
 use lang::module::Module;
 use lang::value::Value;
 use vm::run::fiber::Fiber;
@@ -28,6 +32,14 @@ pub struct VirtualMachine {
 	#[doc(hidden)]
 	pub functions: GarbageCollectedManager<Function>,
 	#[doc(hidden)]
+	pub classes: GarbageCollectedManager<Class>,
+	#[doc(hidden)]
+	pub objects: GarbageCollectedManager<Object>,
+	#[doc(hidden)]
+	pub lists: GarbageCollectedManager<List>,
+	#[doc(hidden)]
+	pub maps: GarbageCollectedManager<Map>,
+	#[doc(hidden)]
 	pub import_paths: Vec<Path>,
 	#[doc(hidden)]
 	pub module_root: Box<Module>,
@@ -50,6 +62,10 @@ pub struct VirtualMachine {
 			
 			VirtualMachine {
 				functions: GarbageCollectedManager::new(),
+				classes: GarbageCollectedManager::new(),
+				objects: GarbageCollectedManager::new(),
+				lists: GarbageCollectedManager::new(),
+				maps: GarbageCollectedManager::new(),
 				import_paths: vec!( Path::new( "modules/" ) ), // todo!
 				implicit: Raw::new( root.get_module( "burn" ).get_module( "implicit" ) ),
 				module_root: root,